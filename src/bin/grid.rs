@@ -5,7 +5,6 @@ use iced::{
     run,
     widget::{Container, container::Style},
 };
-use itertools::Itertools;
 
 struct State<'a> {
     grid: Grid<'a, Message>,
@@ -15,40 +14,31 @@ impl Default for State<'_> {
     fn default() -> Self {
         const DAYS_PER_WEEK: usize = 7;
         let today = 10;
-        let grid = Grid::new()
-            .with_row(["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"])
-            .with_rows(
-                (1..=31)
-                    .map(move |day| {
-                        Factory::from_factory(move || {
-                            let red = day == today;
-                            Cell::from(Text::new(day)).style(if red {
-                                Style {
-                                    background: Some(Background::Color(Color::from_rgb8(
-                                        255, 0, 0,
-                                    ))),
-                                    text_color: Some(Color::from_rgb8(255, 255, 255)),
-                                    ..Style::default()
-                                }
-                            } else {
-                                Style {
-                                    background: Some(Background::Color(Color::from_rgb8(
-                                        255, 255, 255,
-                                    ))),
-                                    text_color: Some(Color::from_rgb8(0, 0, 0)),
-                                    ..Style::default()
-                                }
-                            })
-                        })
+        let grid = Grid::from_iter(
+            (1..=31).map(move |day| {
+                Factory::from_factory(move || {
+                    let red = day == today;
+                    Cell::from(Text::new(day)).style(if red {
+                        Style {
+                            background: Some(Background::Color(Color::from_rgb8(255, 0, 0))),
+                            text_color: Some(Color::from_rgb8(255, 255, 255)),
+                            ..Style::default()
+                        }
+                    } else {
+                        Style {
+                            background: Some(Background::Color(Color::from_rgb8(255, 255, 255))),
+                            text_color: Some(Color::from_rgb8(0, 0, 0)),
+                            ..Style::default()
+                        }
                     })
-                    .chunks(DAYS_PER_WEEK)
-                    .into_iter()
-                    .map(Itertools::collect_vec)
-                    .collect_vec(),
-            )
-            .cell_height(50)
-            .cell_width(50)
-            .padding(5);
+                })
+            }),
+            DAYS_PER_WEEK,
+        )
+        .header(["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"])
+        .cell_height(50)
+        .cell_width(50)
+        .padding(5);
         Self { grid }
     }
 }
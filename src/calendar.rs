@@ -0,0 +1,249 @@
+//! A month view of a calendar, laid out in a [`Grid`] with a row for each week.
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use iced::{
+    Element, Length, advanced,
+    widget::{Text, container::Style, mouse_area, text},
+};
+
+use crate::{Cell, Factory, Grid, layer};
+use std::{fmt, rc::Rc};
+
+/// The number of days in a week, and so the number of columns of days in a month view.
+const DAYS_PER_WEEK: u64 = 7;
+
+/// The default width and height of each day in a month view.
+const DAY_SIZE: f32 = 40.0;
+
+/// A month of a calendar, turned into a [`Grid`] with the names of the days of the week in its
+/// header and a row for each week of the month.
+///
+/// Days of the weeks before and after the month are left empty. Today, the weekend, and the
+/// selected date can each be given their own style, which are layered on top of each other in
+/// that order, with the selected date on top.
+pub struct Calendar<'a, M> {
+    first: NaiveDate,
+    first_weekday: Weekday,
+    week_numbers: bool,
+    today: Option<NaiveDate>,
+    selected: Option<NaiveDate>,
+    day_style: Style,
+    today_style: Option<Style>,
+    weekend_style: Option<Style>,
+    selected_style: Option<Style>,
+    on_day_press: Option<Rc<dyn Fn(NaiveDate) -> M + 'a>>,
+}
+
+impl<M> fmt::Debug for Calendar<'_, M> {
+    /// Format the calendar, showing whether it produces a message when a day is pressed rather
+    /// than the function producing it.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Calendar")
+            .field("first", &self.first)
+            .field("first_weekday", &self.first_weekday)
+            .field("week_numbers", &self.week_numbers)
+            .field("today", &self.today)
+            .field("selected", &self.selected)
+            .field("day_style", &self.day_style)
+            .field("today_style", &self.today_style)
+            .field("weekend_style", &self.weekend_style)
+            .field("selected_style", &self.selected_style)
+            .field("on_day_press", &self.on_day_press.is_some())
+            .finish()
+    }
+}
+
+impl<'a, M> Calendar<'a, M> {
+    /// Create a month view of the given month of the given year, with months counted from 1 for
+    /// January. Weeks start on Sunday.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the month is not between 1 and 12, or the year is out of the range of
+    /// [`NaiveDate`].
+    #[must_use]
+    pub fn month(year: i32, month: u32) -> Self {
+        let first = NaiveDate::from_ymd_opt(year, month, 1)
+            .unwrap_or_else(|| panic!("{year}-{month} is not a valid month"));
+        Self {
+            first,
+            first_weekday: Weekday::Sun,
+            week_numbers: false,
+            today: None,
+            selected: None,
+            day_style: Style::default(),
+            today_style: None,
+            weekend_style: None,
+            selected_style: None,
+            on_day_press: None,
+        }
+    }
+
+    /// Set the day each week starts on, in the first column of the month view.
+    #[must_use]
+    pub const fn first_weekday(mut self, first_weekday: Weekday) -> Self {
+        self.first_weekday = first_weekday;
+        self
+    }
+
+    /// Set whether a column of week numbers is shown before the days. The number of each week is
+    /// its ISO week number, taken from the day in the middle of the week, so weeks starting on
+    /// Monday are numbered exactly like ISO weeks.
+    #[must_use]
+    pub const fn week_numbers(mut self, week_numbers: bool) -> Self {
+        self.week_numbers = week_numbers;
+        self
+    }
+
+    /// Set the date of today, which is drawn with the style set with [`Self::today_style`].
+    #[must_use]
+    pub const fn today(mut self, today: NaiveDate) -> Self {
+        self.today = Some(today);
+        self
+    }
+
+    /// Set the selected date, which is drawn with the style set with [`Self::selected_style`].
+    #[must_use]
+    pub const fn selected(mut self, selected: Option<NaiveDate>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Set the style of every day of the month, beneath the styles of today, the weekend, and the
+    /// selected date.
+    #[must_use]
+    pub fn day_style(mut self, style: impl Into<Style>) -> Self {
+        self.day_style = style.into();
+        self
+    }
+
+    /// Set the style of today, layered on top of the style of the day.
+    #[must_use]
+    pub fn today_style(mut self, style: impl Into<Style>) -> Self {
+        self.today_style = Some(style.into());
+        self
+    }
+
+    /// Set the style of Saturdays and Sundays, layered on top of the style of the day.
+    #[must_use]
+    pub fn weekend_style(mut self, style: impl Into<Style>) -> Self {
+        self.weekend_style = Some(style.into());
+        self
+    }
+
+    /// Set the style of the selected date, layered on top of every other style of the day.
+    #[must_use]
+    pub fn selected_style(mut self, style: impl Into<Style>) -> Self {
+        self.selected_style = Some(style.into());
+        self
+    }
+
+    /// Set the function producing the message for a day of the month being pressed, given its
+    /// date.
+    #[must_use]
+    pub fn on_day_press(mut self, on_day_press: impl Fn(NaiveDate) -> M + 'a) -> Self {
+        self.on_day_press = Some(Rc::new(on_day_press));
+        self
+    }
+
+    /// Find the date shown in the first column of the first week, which may be in the month
+    /// before.
+    fn start(&self) -> NaiveDate {
+        let offset = (self.first.weekday().num_days_from_monday() + 7
+            - self.first_weekday.num_days_from_monday())
+            % 7;
+        self.first - Days::new(u64::from(offset))
+    }
+
+    /// Find the number of weeks the month is spread over.
+    fn weeks(&self) -> usize {
+        let next = self
+            .first
+            .checked_add_months(chrono::Months::new(1))
+            .unwrap_or(NaiveDate::MAX);
+        let days = next.signed_duration_since(self.start()).num_days();
+        usize::try_from(days).unwrap_or_default().div_ceil(7)
+    }
+
+    /// Layer the styles of the given date on top of each other.
+    fn style(&self, date: NaiveDate) -> Style {
+        let mut style = self.day_style;
+        if let Some(today_style) = self.today_style
+            && self.today == Some(date)
+        {
+            style = layer(&style, &today_style);
+        }
+        if let Some(weekend_style) = self.weekend_style
+            && matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+        {
+            style = layer(&style, &weekend_style);
+        }
+        if let Some(selected_style) = self.selected_style
+            && self.selected == Some(date)
+        {
+            style = layer(&style, &selected_style);
+        }
+        style
+    }
+}
+
+impl<'a, M: Clone + 'a, T: text::Catalog + 'a, R: advanced::text::Renderer + 'a>
+    From<Calendar<'a, M>> for Grid<'a, M, T, R>
+{
+    /// Lay out the month view in a grid, with each day as wide and as tall as forty pixels. The
+    /// size of the days can be changed on the grid like for any other grid.
+    fn from(calendar: Calendar<'a, M>) -> Self {
+        let start = calendar.start();
+        let week_numbers = usize::from(calendar.week_numbers);
+        let header = (0..DAYS_PER_WEEK).map(|day| {
+            let name = (start + Days::new(day)).weekday().to_string();
+            Factory::from_factory(move || Cell::from(Text::new(name.clone())))
+        });
+        let header: Vec<_> = (week_numbers > 0)
+            .then(|| Factory::from_factory(|| Cell::from(Text::new("Wk"))))
+            .into_iter()
+            .chain(header)
+            .collect();
+        let month = calendar.first.month();
+        let calendar = Rc::new(calendar);
+        #[allow(clippy::cast_possible_truncation)]
+        Self::from_fn(
+            calendar.weeks(),
+            week_numbers + DAYS_PER_WEEK as usize,
+            move |week, column| {
+                let week_start = start + Days::new(week as u64 * DAYS_PER_WEEK);
+                let Some(day) = column.checked_sub(week_numbers) else {
+                    let week = (week_start + Days::new(DAYS_PER_WEEK / 2))
+                        .iso_week()
+                        .week();
+                    return Cell::from(Text::new(week));
+                };
+                let date = week_start + Days::new(day as u64);
+                if date.month() != month {
+                    return Cell::empty();
+                }
+                let day: Element<'a, M, T, R> = Text::new(date.day())
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .center()
+                    .into();
+                let day = match &calendar.on_day_press {
+                    Some(on_day_press) => mouse_area(day).on_press(on_day_press(date)).into(),
+                    None => day,
+                };
+                Cell::from(day).style(calendar.style(date))
+            },
+        )
+        .header(header)
+        .cell_width(DAY_SIZE)
+        .cell_height(DAY_SIZE)
+    }
+}
+
+impl<'a, M: Clone + 'a, T: text::Catalog + 'a, R: advanced::text::Renderer + 'a>
+    From<Calendar<'a, M>> for Element<'a, M, T, R>
+{
+    /// Turn the month view into a widget, laid out like a [`Grid`] made from it.
+    fn from(calendar: Calendar<'a, M>) -> Self {
+        Grid::from(calendar).into()
+    }
+}
@@ -0,0 +1,34 @@
+//! Factories of common kinds of cells of a [`Grid`](crate::Grid), which give the cells they create
+//! the row and column of each cell.
+use crate::{Cell, Factory};
+use iced::{
+    Length, Padding, advanced,
+    widget::{self, Button, text},
+};
+
+/// Create a factory of compact buttons with the given label, which fill their cells and produce
+/// the message the given function gives for the row and column of the cell that is pressed.
+///
+/// This suits columns of actions, like a column of buttons for deleting rows. The text content of
+/// each cell is the label.
+pub fn button<
+    'a,
+    M: Clone + 'a,
+    T: widget::button::Catalog + text::Catalog + 'a,
+    R: advanced::text::Renderer + 'a,
+>(
+    label: impl Into<String>,
+    on_press: impl Fn(usize, usize) -> M + 'a,
+) -> Factory<'a, M, T, R> {
+    let label = label.into();
+    Factory::from_indexed(move |row, column| {
+        Cell::from(
+            Button::new(text(label.clone()).center().width(Length::Fill))
+                .padding(Padding::from([2, 6]))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .on_press(on_press(row, column)),
+        )
+        .text(label.clone())
+    })
+}
@@ -0,0 +1,33 @@
+//! Factories of the cells of common kinds of columns of a [`Grid`](crate::Grid).
+//!
+//! Each factory can be given to each row of a grid, or repeated down a column with
+//! [`Grid::with_column`](crate::Grid::with_column).
+use crate::{Cell, Factory};
+use iced::{
+    advanced,
+    widget::{self, Checkbox},
+};
+use std::rc::Rc;
+
+/// Create a factory of the cells of a column of checkboxes centered in their cells.
+///
+/// This suits columns for selecting rows or marking them as done. The checkbox of each row is
+/// checked as the given function says for the index of the row, and toggling it produces the
+/// given message for the index of the row and whether it should be checked. The text content of
+/// each cell is `true` or `false`, as the checkbox is checked.
+pub fn checkbox<'a, M: 'a, T: widget::checkbox::Catalog + 'a, R: advanced::text::Renderer + 'a>(
+    checked: impl Fn(usize) -> bool + 'a,
+    on_toggle: impl Fn(usize, bool) -> M + 'a,
+) -> Factory<'a, M, T, R> {
+    let on_toggle = Rc::new(on_toggle);
+    Factory::from_indexed(move |row, _| {
+        let checked = checked(row);
+        let on_toggle = on_toggle.clone();
+        Cell::from(
+            Checkbox::new("", checked)
+                .spacing(0)
+                .on_toggle(move |checked| on_toggle(row, checked)),
+        )
+        .text(checked.to_string())
+    })
+}
@@ -0,0 +1,280 @@
+//! Formats turning the raw values of cells into text, so the values of a [`Grid`] are shown
+//! consistently however its cells are created.
+//!
+//! [`Grid`]: crate::Grid
+use iced::{
+    advanced,
+    alignment::Horizontal,
+    widget::{Text, text},
+};
+
+use crate::{Cell, Factory};
+use std::fmt;
+
+/// A raw value of a cell, to be shown as text with a [`CellFormat`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A number.
+    Number(f64),
+    /// Text, which is shown as it is.
+    Text(String),
+    /// A date.
+    #[cfg(feature = "chrono")]
+    Date(chrono::NaiveDate),
+    /// A date and a time of day.
+    #[cfg(feature = "chrono")]
+    DateTime(chrono::NaiveDateTime),
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<f32> for Value {
+    fn from(value: f32) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+impl From<i32> for Value {
+    fn from(value: i32) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+impl From<u32> for Value {
+    fn from(value: u32) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+impl From<i64> for Value {
+    /// Create a number from the given integer, which loses precision for integers too large to
+    /// be stored exactly in an [`f64`].
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: i64) -> Self {
+        Self::Number(value as f64)
+    }
+}
+
+impl From<usize> for Value {
+    /// Create a number from the given integer, which loses precision for integers too large to
+    /// be stored exactly in an [`f64`].
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: usize) -> Self {
+        Self::Number(value as f64)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_owned())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDate> for Value {
+    fn from(value: chrono::NaiveDate) -> Self {
+        Self::Date(value)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::NaiveDateTime> for Value {
+    fn from(value: chrono::NaiveDateTime) -> Self {
+        Self::DateTime(value)
+    }
+}
+
+impl fmt::Display for Value {
+    /// Format the value as it is, without a [`CellFormat`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(number) => number.fmt(f),
+            Self::Text(text) => text.fmt(f),
+            #[cfg(feature = "chrono")]
+            Self::Date(date) => date.fmt(f),
+            #[cfg(feature = "chrono")]
+            Self::DateTime(date_time) => date_time.fmt(f),
+        }
+    }
+}
+
+/// A way of showing the raw values of cells as text, like the number formats of a spreadsheet.
+///
+/// Each format applies to one kind of [`Value`], and values of other kinds are shown as they are,
+/// so a column of numbers with a few empty text values can still be given a number format.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum CellFormat {
+    /// Values are shown as they are.
+    #[default]
+    Plain,
+    /// Numbers are shown with the given number of digits after the decimal point, with the given
+    /// separator between each group of three digits before it, if any.
+    Number {
+        /// The number of digits after the decimal point.
+        precision: usize,
+        /// The separator between each group of three digits before the decimal point.
+        separator: Option<char>,
+    },
+    /// Numbers are shown as percentages of one, with the given number of digits after the
+    /// decimal point, so a half is shown as `50%`.
+    Percent {
+        /// The number of digits after the decimal point.
+        precision: usize,
+    },
+    /// Dates, and dates with a time of day, are shown with the given format, which is written
+    /// like the formats of `strftime`. Dates are shown as they are if the format is invalid.
+    #[cfg(feature = "chrono")]
+    Date(String),
+}
+
+impl CellFormat {
+    /// Create a format showing numbers with the given number of digits after the decimal point.
+    #[must_use]
+    pub const fn number(precision: usize) -> Self {
+        Self::Number {
+            precision,
+            separator: None,
+        }
+    }
+
+    /// Create a format showing numbers with the given number of digits after the decimal point
+    /// and the given separator between each group of three digits before it, like `1,234.50`.
+    #[must_use]
+    pub const fn thousands(precision: usize, separator: char) -> Self {
+        Self::Number {
+            precision,
+            separator: Some(separator),
+        }
+    }
+
+    /// Create a format showing numbers as percentages with the given number of digits after the
+    /// decimal point.
+    #[must_use]
+    pub const fn percent(precision: usize) -> Self {
+        Self::Percent { precision }
+    }
+
+    /// Create a format showing dates with the given format, written like the formats of
+    /// `strftime`, like `%Y-%m-%d`.
+    #[cfg(feature = "chrono")]
+    #[must_use]
+    pub fn date(format: impl Into<String>) -> Self {
+        Self::Date(format.into())
+    }
+
+    /// Show the given value as text with the format.
+    #[must_use]
+    pub fn format(&self, value: impl Into<Value>) -> String {
+        let value = value.into();
+        match (self, &value) {
+            (_, Value::Number(number)) if !number.is_finite() => value.to_string(),
+            (
+                &Self::Number {
+                    precision,
+                    separator,
+                },
+                &Value::Number(number),
+            ) => {
+                let formatted = format!("{number:.precision$}");
+                match separator {
+                    Some(separator) => group(&formatted, separator),
+                    None => formatted,
+                }
+            }
+            (&Self::Percent { precision }, &Value::Number(number)) => {
+                format!("{:.precision$}%", number * 100.0)
+            }
+            #[cfg(feature = "chrono")]
+            (Self::Date(format), Value::Date(date)) => {
+                write_date(date.format(format)).unwrap_or_else(|| value.to_string())
+            }
+            #[cfg(feature = "chrono")]
+            (Self::Date(format), Value::DateTime(date_time)) => {
+                write_date(date_time.format(format)).unwrap_or_else(|| value.to_string())
+            }
+            _ => value.to_string(),
+        }
+    }
+
+    /// Check whether the format shows the given value as a number, which is lined up on the right
+    /// of its cell.
+    const fn is_numeric(&self, value: &Value) -> bool {
+        matches!(self, Self::Number { .. } | Self::Percent { .. })
+            && matches!(value, Value::Number(_))
+    }
+}
+
+/// Write the given formatted date into a string, which fails instead of panicking when the format
+/// of the date is invalid.
+#[cfg(feature = "chrono")]
+fn write_date(formatted: impl fmt::Display) -> Option<String> {
+    use fmt::Write;
+    let mut output = String::new();
+    write!(output, "{formatted}").ok()?;
+    Some(output)
+}
+
+/// Put the given separator between each group of three digits before the decimal point of the
+/// given formatted number.
+fn group(formatted: &str, separator: char) -> String {
+    let (sign, unsigned) = formatted
+        .strip_prefix('-')
+        .map_or(("", formatted), |unsigned| ("-", unsigned));
+    let (whole, fraction) = unsigned
+        .find('.')
+        .map_or((unsigned, ""), |point| unsigned.split_at(point));
+    let mut grouped = String::from(sign);
+    for (index, digit) in whole.chars().enumerate() {
+        if index > 0 && (whole.len() - index) % 3 == 0 {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped.push_str(fraction);
+    grouped
+}
+
+impl<'a, M: 'a, T: text::Catalog + 'a, R: advanced::text::Renderer + 'a> Cell<'a, M, T, R> {
+    /// Create a new grid cell showing the given value as text with the given format. Numbers are
+    /// lined up on the right of the cell, and the formatted value is the text content of the cell.
+    #[must_use]
+    pub fn formatted(value: impl Into<Value>, format: &CellFormat) -> Self {
+        let value = value.into();
+        let align_x = if format.is_numeric(&value) {
+            Horizontal::Right
+        } else {
+            Horizontal::Center
+        };
+        let formatted = format.format(value);
+        Self::from(Text::new(formatted.clone()))
+            .text(formatted)
+            .align_x(align_x)
+    }
+}
+
+impl<'a, M: 'a, T: text::Catalog + 'a, R: advanced::text::Renderer + 'a> Factory<'a, M, T, R> {
+    /// Create a new factory that creates cells showing the value the given function gives for the
+    /// row and column of each cell as text with the given format, like the cells created with
+    /// [`Cell::formatted`].
+    ///
+    /// This keeps the raw values of a grid in one place, like a [`GridModel`], with the way they
+    /// are shown in another, instead of formatting them in each factory.
+    ///
+    /// [`GridModel`]: crate::GridModel
+    pub fn formatted<V: Into<Value>>(
+        value: impl Fn(usize, usize) -> V + 'a,
+        format: CellFormat,
+    ) -> Self {
+        Self::from_indexed(move |row, column| Cell::formatted(value(row, column), &format))
+    }
+}
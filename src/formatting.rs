@@ -0,0 +1,71 @@
+//! Conditional formatting of the cells of a [`Grid`](crate::Grid), styling the cells that match
+//! rules like the conditional formatting of a spreadsheet.
+use iced::widget::container::Style;
+use std::{fmt, rc::Rc};
+
+/// A rule styling the cells of a grid that match it, given to a grid with
+/// [`Grid::rule`](crate::Grid::rule).
+///
+/// The style of a rule is layered on top of the style of each cell it matches, so parts of the
+/// style that are left unset, like the background, stay the same. Rules are applied in the order
+/// they are given to the grid, so the styles of later rules are layered on top of the styles of
+/// earlier ones.
+#[derive(Clone)]
+pub struct Rule<'a> {
+    predicate: Rc<dyn Fn(usize, usize) -> bool + 'a>,
+    style: Style,
+    stop: bool,
+}
+
+impl fmt::Debug for Rule<'_> {
+    /// Format the rule, leaving out its predicate.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rule")
+            .field("style", &self.style)
+            .field("stop", &self.stop)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Rule<'a> {
+    /// Create a new rule matching the cells the given predicate is true for, given the row and
+    /// column of each cell. The rule has no style until one is set with [`Self::style`].
+    #[must_use]
+    pub fn new(predicate: impl Fn(usize, usize) -> bool + 'a) -> Self {
+        Self {
+            predicate: Rc::new(predicate),
+            style: Style::default(),
+            stop: false,
+        }
+    }
+
+    /// Set the style of the cells the rule matches.
+    #[must_use]
+    pub fn style(mut self, style: impl Into<Style>) -> Self {
+        self.style = style.into();
+        self
+    }
+
+    /// Set whether the rules after this rule are skipped for the cells it matches, like the "stop
+    /// if true" option of the conditional formatting of a spreadsheet.
+    #[must_use]
+    pub const fn stop(mut self, stop: bool) -> Self {
+        self.stop = stop;
+        self
+    }
+
+    /// Check whether the rule matches the cell at the given row and column.
+    pub(crate) fn matches(&self, row: usize, column: usize) -> bool {
+        (self.predicate)(row, column)
+    }
+
+    /// Get the style of the cells the rule matches.
+    pub(crate) const fn style_of(&self) -> Style {
+        self.style
+    }
+
+    /// Check whether the rules after this rule are skipped for the cells it matches.
+    pub(crate) const fn stops(&self) -> bool {
+        self.stop
+    }
+}
@@ -0,0 +1,162 @@
+//! A heatmap, laid out in a [`Grid`] with the background of each cell colored by its value.
+use iced::{
+    Background, Color, Length, advanced,
+    widget::{Space, Text, container::Style, text},
+};
+
+use crate::{Cell, Factory, Grid};
+use std::{fmt, ops::RangeInclusive, rc::Rc};
+
+/// The default width and height of each cell of a heatmap.
+const CELL_SIZE: f32 = 12.0;
+
+/// The default gutter between the cells of a heatmap.
+const GUTTER: f32 = 2.0;
+
+/// A grid of values, turned into a [`Grid`] where the background of each cell is the color a
+/// gradient gives its value, with optional labels along the rows and columns.
+///
+/// The gradient is given each value scaled to between zero and one, where zero is the smallest
+/// value and one is the largest value, unless the range of values is set with [`Self::range`].
+/// Values that are not numbers are left empty.
+pub struct Heatmap<'a> {
+    values: Vec<Vec<f32>>,
+    gradient: Rc<dyn Fn(f32) -> Color + 'a>,
+    range: Option<RangeInclusive<f32>>,
+    row_labels: Option<Vec<String>>,
+    column_labels: Option<Vec<String>>,
+}
+
+impl fmt::Debug for Heatmap<'_> {
+    /// Format the heatmap, leaving out the gradient.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Heatmap")
+            .field("values", &self.values)
+            .field("range", &self.range)
+            .field("row_labels", &self.row_labels)
+            .field("column_labels", &self.column_labels)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Heatmap<'a> {
+    /// Create a new heatmap of the given rows of values, colored by the given gradient.
+    #[must_use]
+    pub fn new(
+        values: impl IntoIterator<Item = impl IntoIterator<Item = f32>>,
+        gradient: impl Fn(f32) -> Color + 'a,
+    ) -> Self {
+        Self {
+            values: values
+                .into_iter()
+                .map(|row| row.into_iter().collect())
+                .collect(),
+            gradient: Rc::new(gradient),
+            range: None,
+            row_labels: None,
+            column_labels: None,
+        }
+    }
+
+    /// Set the range of values the gradient is spread over, in place of the range from the
+    /// smallest value to the largest value. Values outside of the range are clamped to it.
+    #[must_use]
+    pub const fn range(mut self, range: RangeInclusive<f32>) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Set the labels shown before each row, in a column of their own.
+    #[must_use]
+    pub fn row_labels(mut self, labels: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.row_labels = Some(labels.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set the labels shown above each column, in the header of the grid.
+    #[must_use]
+    pub fn column_labels(mut self, labels: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.column_labels = Some(labels.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Find the smallest and largest values of the range of the heatmap.
+    fn bounds(&self) -> (f32, f32) {
+        self.range.clone().map_or_else(
+            || {
+                self.values
+                    .iter()
+                    .flatten()
+                    .filter(|value| !value.is_nan())
+                    .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &value| {
+                        (min.min(value), max.max(value))
+                    })
+            },
+            RangeInclusive::into_inner,
+        )
+    }
+}
+
+/// Scale the given value to between zero and one within the range between the given smallest and
+/// largest values.
+fn scale(value: f32, (min, max): (f32, f32)) -> f32 {
+    if max > min {
+        ((value - min) / (max - min)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+/// Create a factory for a cell with a label in it.
+fn label<'a, M: 'a, T: text::Catalog + 'a, R: advanced::text::Renderer + 'a>(
+    label: String,
+) -> Factory<'a, M, T, R> {
+    Factory::from_factory(move || Cell::from(Text::new(label.clone())).text(label.clone()))
+}
+
+impl<'a, M: 'a, T: text::Catalog + 'a, R: advanced::text::Renderer + 'a> From<Heatmap<'a>>
+    for Grid<'a, M, T, R>
+{
+    /// Lay out the heatmap in a grid, with each cell as wide and as tall as twelve pixels and a
+    /// gutter of two pixels between them. Labels are as large as their text.
+    fn from(heatmap: Heatmap<'a>) -> Self {
+        let labelled = heatmap.row_labels.is_some();
+        let mut grid = Self::new()
+            .cell_width(CELL_SIZE)
+            .cell_height(CELL_SIZE)
+            .gutter(GUTTER);
+        if let Some(labels) = &heatmap.column_labels {
+            let corner = labelled.then(Factory::empty);
+            grid = grid
+                .header(corner.into_iter().chain(labels.iter().cloned().map(label)))
+                .header_height(Length::Shrink);
+        }
+        if labelled {
+            grid = grid.column_widths([Length::Shrink]);
+        }
+        let bounds = heatmap.bounds();
+        let mut row_labels = heatmap.row_labels.clone().unwrap_or_default().into_iter();
+        for row in &heatmap.values {
+            let cells = row.iter().map(|&value| {
+                let text = (!value.is_nan()).then(|| value.to_string());
+                let style = Some(value).filter(|value| !value.is_nan()).map(|value| {
+                    let color = (heatmap.gradient)(scale(value, bounds));
+                    Style::default().background(Background::Color(color))
+                });
+                Factory::from_factory(move || {
+                    let mut cell = Cell::from(Space::new(0, 0));
+                    if let Some(text) = &text {
+                        cell = cell.text(text.clone());
+                    }
+                    match style {
+                        Some(style) => cell.style(style),
+                        None => cell,
+                    }
+                })
+            });
+            let row_label = labelled.then(|| label(row_labels.next().unwrap_or_default()));
+            grid = grid.with_row(row_label.into_iter().chain(cells));
+        }
+        grid
+    }
+}
@@ -0,0 +1,3069 @@
+//! The widget a [`Grid`](crate::Grid) turns into on each `view` cycle, after every factory has been
+//! called and every cell has been placed.
+use iced::{
+    Alignment, Background, Border, Color, Element, Event, Length, Pixels, Point, Rectangle, Size,
+    Task, Vector,
+    advanced::{
+        self, Clipboard, Layout, Shell, Widget, clipboard, layout, mouse, overlay, renderer,
+        widget::{self, Operation, Tree, operation, tree},
+    },
+    alignment::Horizontal,
+    border::Radius,
+    event, keyboard,
+    time::{Duration, Instant},
+    widget::container,
+    window,
+};
+
+use crate::{
+    Area, Cell, Distribution, FrameStyleFn, Grid, OnHover, OnPaste, OnRangeSelect, RowTransition,
+    SelectionChanged, SortOrder, Traversal, layer, parse_records,
+};
+use std::{
+    any::Any,
+    cell::Ref,
+    collections::{HashMap, HashSet},
+    ops::Range,
+    rc::Rc,
+};
+
+/// The part of a grid a cell belongs to, along with where the cell is in that part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    /// A cell in the header, in the given column.
+    Header(usize),
+    /// A cell in the rows of the grid, at the given row and column.
+    Body(usize, usize),
+    /// The row of controls for changing pages below the rows of a paginated grid.
+    Pager,
+    /// A cell in the footer, in the given column.
+    Footer(usize),
+    /// The number of the given row, in the column of row numbers.
+    RowNumber(usize),
+    /// The letter naming the given column, in the row of column letters.
+    ColumnLetter(usize),
+    /// The empty cell in the corner above the column of row numbers.
+    Corner,
+}
+
+/// A band of rows of a grid, which scroll differently: the header stays at the top of a grid that
+/// scrolls and the footer stays at the bottom, along with the pager below it, while the body
+/// scrolls between them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Band {
+    Header,
+    Body,
+    Footer,
+}
+
+/// A cell to be placed in a grid along with the part of the grid it belongs to, or nothing for a
+/// slot left empty.
+pub type Placement<'a, M, T, R> = Option<(Part, Cell<'a, M, T, R>)>;
+
+/// The elements drawn in the gutters between rows or columns, along with the track after the
+/// gutter each of them is in.
+pub type Separators<'a, M, T, R> = Vec<(usize, Element<'a, M, T, R>)>;
+
+/// A cell that has been given a position in the grid.
+pub struct Slot<'a, M, T, R> {
+    pub cell: Cell<'a, M, T, R>,
+    pub part: Part,
+    pub row: usize,
+    pub column: usize,
+}
+
+/// A grid of placed cells, ready to be laid out and drawn.
+pub struct Instance<'a, M, T, R> {
+    pub slots: Vec<Slot<'a, M, T, R>>,
+    pub column_widths: Vec<Length>,
+    pub row_heights: Vec<Length>,
+    pub column_limits: Vec<(f32, f32)>,
+    pub row_limits: Vec<(f32, f32)>,
+    pub aspect_ratio: Option<f32>,
+    pub column_gutter: f32,
+    pub row_gutter: f32,
+    pub padding: f32,
+    pub id: Option<widget::Id>,
+    pub justify_content: Distribution,
+    pub align_content: Distribution,
+    pub lines: Option<(f32, Color)>,
+    pub row_separators: Separators<'a, M, T, R>,
+    pub column_separators: Separators<'a, M, T, R>,
+    pub collapse_borders: bool,
+    pub corner_radius: f32,
+    pub on_press: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    pub on_double_press: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    pub double_press_interval: Duration,
+    pub scroll_animation: Option<Duration>,
+    pub row_transition: Option<(RowTransition, Duration)>,
+    pub on_row_press: Option<Rc<dyn Fn(usize) -> M + 'a>>,
+    pub loading_rows: Range<usize>,
+    pub on_request_rows: Option<Rc<dyn Fn(Range<usize>) -> M + 'a>>,
+    pub on_row_select: Option<Rc<dyn Fn(usize) -> M + 'a>>,
+    pub on_column_select: Option<Rc<dyn Fn(usize) -> M + 'a>>,
+    pub on_right_press: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    pub on_paint: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    pub on_hover: Option<OnHover<'a, M>>,
+    pub on_range_select: Option<OnRangeSelect<'a, M>>,
+    pub on_paste: Option<OnPaste<'a, M>>,
+    pub selected: Option<(usize, usize)>,
+    pub on_select: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    pub selection: HashSet<(usize, usize)>,
+    pub copying: std::cell::Cell<bool>,
+    pub on_selection_change: Option<Rc<dyn Fn(SelectionChanged) -> M + 'a>>,
+    pub on_focus_cell: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    pub tab_traversal: Traversal,
+    pub focus_ring: Option<Border>,
+    pub header_rows: usize,
+    pub footer_rows: usize,
+    pub pager_rows: usize,
+    pub body_rows: Vec<usize>,
+    pub row_keys: Vec<Option<u64>>,
+    pub on_row_reorder: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    pub on_column_reorder: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    pub on_column_resize: Option<Rc<dyn Fn(usize, Pixels) -> M + 'a>>,
+    pub window: Range<usize>,
+    pub reflow: Option<usize>,
+    pub viewport_height: Option<f32>,
+    pub scrollable: bool,
+    pub frozen_columns: usize,
+    pub number_columns: usize,
+    pub frame_style: Option<FrameStyleFn<'a, T>>,
+    pub sort: Option<(usize, SortOrder)>,
+    pub sortable: Vec<bool>,
+    pub on_sort: Option<Rc<dyn Fn(usize, SortOrder) -> M + 'a>>,
+    pub right_to_left: bool,
+}
+
+/// The state of a grid, kept between the times it is laid out, drawn and given events.
+#[derive(Debug, Default)]
+pub struct State {
+    /// What each child of the grid showed, to keep the state of children that move.
+    children: Vec<Child>,
+    /// Where the columns ended up the last time the grid was laid out.
+    columns: Vec<Track>,
+    /// Where the rows ended up the last time the grid was laid out.
+    rows: Vec<Track>,
+    /// How wide the gutters ended up once the tracks were spread out.
+    gutters: Vector,
+    /// How far the grid is scrolled, if it scrolls.
+    scroll: Vector,
+    /// The cell the grid should be scrolled to the next time it is laid out, if any.
+    scroll_target: Option<(usize, usize)>,
+    /// How the grid is being scrolled smoothly, if it is.
+    scroll_animation: Option<ScrollAnimation>,
+    /// Where each row of the body was, by its identity, to animate the rows that moved.
+    row_offsets: Option<HashMap<u64, f32>>,
+    /// The rows being animated, by their identity.
+    row_animations: HashMap<u64, RowAnimation>,
+    /// How far the grid can be scrolled.
+    max_scroll: Vector,
+    /// The modifier keys held, which change how pressing cells changes the selection.
+    modifiers: keyboard::Modifiers,
+    /// Whether the grid is focused, to move between its cells with the keyboard.
+    focused: bool,
+    /// The cell moved to with the keyboard or pressed last, if any.
+    focused_cell: Option<(usize, usize)>,
+    /// The row or column being dragged, if any.
+    drag: Option<Drag>,
+    /// The column being resized, if any.
+    resize: Option<Resize>,
+    /// The block of cells being selected by dragging, if any.
+    range: Option<RangeSelection>,
+    /// Where cells are being painted, if they are.
+    paint: Option<Paint>,
+    /// The cell under the cursor, if any.
+    hovered: Option<(usize, usize)>,
+    /// When the last cell was pressed, and which cell it was.
+    last_press: Option<(Instant, (usize, usize))>,
+    /// The rows last requested to be loaded, if any.
+    requested_rows: Option<Range<usize>>,
+    /// How wide the grid was allowed to be.
+    available_width: f32,
+}
+
+/// What a child of a grid shows, which tells it apart from the other children of the grid when the
+/// grid is rebuilt, so that it keeps its state even if it moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Child {
+    /// The cell in the given column of the row with the given identity.
+    Cell(u64, usize),
+    /// A cell in a part of the grid other than its body.
+    Part(Part),
+    /// The separator above the given row.
+    RowSeparator(usize),
+    /// The separator before the given column.
+    ColumnSeparator(usize),
+}
+
+/// A grid scrolling smoothly from one scroll offset to another, starting at the given time.
+#[derive(Debug, Clone, Copy)]
+struct ScrollAnimation {
+    from: Vector,
+    to: Vector,
+    start: Instant,
+}
+
+/// A row of a grid being animated, starting at the given time, from the given distance above
+/// where it ends up, fading in if it is being added, and how far along the animation is.
+#[derive(Debug, Clone, Copy)]
+struct RowAnimation {
+    start: Instant,
+    offset: f32,
+    appearing: bool,
+    progress: f32,
+}
+
+/// Find how far along an animation of the given duration that started at the given time is at the
+/// given time, from zero to one.
+fn progress(start: Instant, now: Instant, duration: Duration) -> f32 {
+    if duration.is_zero() {
+        return 1.0;
+    }
+    (now.saturating_duration_since(start).as_secs_f32() / duration.as_secs_f32()).min(1.0)
+}
+
+/// Ease an animation out, so it starts quickly and slows down towards its end.
+fn ease(progress: f32) -> f32 {
+    1.0 - (1.0 - progress).powi(3)
+}
+
+/// A row or column being dragged to a new position.
+#[derive(Debug, Clone, Copy)]
+struct Drag {
+    line: Line,
+    origin: Point,
+    moved: bool,
+}
+
+/// A row or column of the cells of a grid, by its index.
+#[derive(Debug, Clone, Copy)]
+enum Line {
+    Row(usize),
+    Column(usize),
+}
+
+impl Line {
+    /// Find the position of a point along the axis the line is dragged along.
+    const fn along(self, point: Point) -> f32 {
+        match self {
+            Self::Row(_) => point.y,
+            Self::Column(_) => point.x,
+        }
+    }
+}
+
+/// A column being resized by dragging the gutter on its right.
+#[derive(Debug, Clone, Copy)]
+struct Resize {
+    column: usize,
+    origin: f32,
+    initial: f32,
+    width: f32,
+}
+
+/// A block of cells being selected by dragging from the cell at its start to the cell at its end,
+/// given as rows and columns.
+#[derive(Debug, Clone, Copy)]
+struct RangeSelection {
+    start: (usize, usize),
+    end: (usize, usize),
+}
+
+/// Cells being painted by moving the cursor while the left mouse button is held, remembering where
+/// the cursor was last and the row and column of the cell painted last.
+#[derive(Debug, Clone, Copy)]
+struct Paint {
+    position: Point,
+    cell: (usize, usize),
+}
+
+/// How wide the area around the gutter on the right of each column that can be dragged to resize the
+/// column is.
+const RESIZE_HANDLE_WIDTH: f32 = 6.0;
+
+/// How many rows above and below the visible rows of a virtualized grid have their cells created.
+const OVERSCAN: usize = 2;
+
+/// How far a grid scrolls for each line the mouse wheel scrolls by.
+const LINE_HEIGHT: f32 = 60.0;
+
+/// How far the cursor has to move after pressing a row or column before it starts being dragged.
+const DRAG_THRESHOLD: f32 = 4.0;
+
+/// The width of the line drawn where a dragged row or column would be dropped.
+const DROP_INDICATOR_WIDTH: f32 = 2.0;
+
+/// How opaque the rectangle drawn over a block of cells being selected by dragging is.
+const RANGE_SELECTION_ALPHA: f32 = 0.15;
+
+/// The width of the triangle drawn in the header of the column the rows are sorted by.
+const SORT_INDICATOR_WIDTH: f32 = 8.0;
+
+impl operation::Focusable for State {
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+/// A row or column of a grid after it has been laid out, relative to the top-left corner of the
+/// grid.
+#[derive(Debug, Clone, Copy)]
+struct Track {
+    offset: f32,
+    size: f32,
+}
+
+impl Track {
+    /// Find the offset of the end of the track.
+    fn end(self) -> f32 {
+        self.offset + self.size
+    }
+}
+
+/// Make the given style of a cell more transparent, multiplying the opacity of its colors by the
+/// given factor.
+fn faded(style: &container::Style, alpha: f32) -> container::Style {
+    container::Style {
+        text_color: style.text_color.map(|color| color.scale_alpha(alpha)),
+        background: style
+            .background
+            .map(|background| background.scale_alpha(alpha)),
+        border: Border {
+            color: style.border.color.scale_alpha(alpha),
+            ..style.border
+        },
+        shadow: iced::Shadow {
+            color: style.shadow.color.scale_alpha(alpha),
+            ..style.shadow
+        },
+    }
+}
+
+/// Grow the bounds of the cell in the given slot up and to the left by the width of its border,
+/// unless it is in the first row or column, so that its border is drawn over the border of the
+/// cells above it and before it.
+fn collapsed<M, T, R>(bounds: Rectangle, slot: &Slot<'_, M, T, R>, border: f32) -> Rectangle {
+    let x = if slot.column > 0 { border } else { 0.0 };
+    let y = if slot.row > 0 { border } else { 0.0 };
+    Rectangle {
+        x: bounds.x - x,
+        y: bounds.y - y,
+        width: bounds.width + x,
+        height: bounds.height + y,
+    }
+}
+
+/// Find which gap between the given tracks, counting from the gap before the first track, is closest
+/// to the given position along them.
+fn gap_at(tracks: &[Track], position: f32) -> usize {
+    tracks
+        .iter()
+        .take_while(|track| track.size.mul_add(0.5, track.offset) < position)
+        .count()
+}
+
+/// Find the position of the given gap between the given tracks.
+fn gap_offset(tracks: &[Track], gap: usize) -> f32 {
+    match (
+        gap.checked_sub(1).and_then(|track| tracks.get(track)),
+        tracks.get(gap),
+    ) {
+        (Some(before), Some(after)) => f32::midpoint(before.end(), after.offset),
+        (Some(before), None) => before.end(),
+        (None, Some(after)) => after.offset,
+        (None, None) => 0.0,
+    }
+}
+
+/// Draw a triangle at the right of the header cell with the given bounds, pointing up if the rows
+/// are sorted in ascending order and down if they are sorted in descending order.
+fn draw_sort_indicator(
+    renderer: &mut impl advanced::Renderer,
+    bounds: Rectangle,
+    order: SortOrder,
+    color: Color,
+) {
+    let center = Point::new(
+        bounds.x + bounds.width - SORT_INDICATOR_WIDTH,
+        bounds.center_y(),
+    );
+    let steps: u8 = 4;
+    let step_height = SORT_INDICATOR_WIDTH / 2.0 / f32::from(steps);
+    for step in 0..steps {
+        let step = match order {
+            SortOrder::Ascending => step,
+            SortOrder::Descending => steps - 1 - step,
+        };
+        let width = SORT_INDICATOR_WIDTH * f32::from(step + 1) / f32::from(steps);
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: Rectangle::new(
+                    Point::new(
+                        center.x - width / 2.0,
+                        center.y - SORT_INDICATOR_WIDTH / 4.0 + step_height * f32::from(step),
+                    ),
+                    Size::new(width, step_height),
+                ),
+                ..renderer::Quad::default()
+            },
+            color,
+        );
+    }
+}
+
+/// Place the cells of each row from left to right, skipping over any slots that are already
+/// occupied by cells spanning down from the rows above. Empty slots take up a single slot without
+/// placing anything in it.
+///
+/// Cells placed in one of the given areas are placed first, filling their area, so the other
+/// cells are placed around them. The rows of the areas are counted from below the given number
+/// of header rows.
+///
+/// If the placement is dense, each cell of the body is placed in the first hole it fits in
+/// within the columns placed so far, starting from the first row below the header, and only
+/// falls back to its own row if there is no such hole.
+///
+/// Returns the placed cells, along with the number of rows and columns the grid ends up with.
+pub fn place<'a, M, T, R>(
+    rows: impl IntoIterator<Item = impl IntoIterator<Item = Placement<'a, M, T, R>>>,
+    areas: &[Area],
+    header_rows: usize,
+    dense: bool,
+) -> (Vec<Slot<'a, M, T, R>>, usize, usize) {
+    let mut occupied: Vec<Vec<bool>> = Vec::new();
+    let mut slots = Vec::new();
+    let mut row_count = 0;
+    let mut column_count = 0;
+    let rows: Vec<Vec<_>> = rows
+        .into_iter()
+        .map(|cells| {
+            cells
+                .into_iter()
+                .filter_map(|cell| {
+                    let Some((part, mut cell)) = cell else {
+                        return Some(None);
+                    };
+                    let Some(area) = cell
+                        .area
+                        .as_deref()
+                        .and_then(|name| areas.iter().find(|area| area.name == name))
+                    else {
+                        return Some(Some((part, cell)));
+                    };
+                    let row = header_rows + area.rows.start;
+                    let column = area.columns.start;
+                    cell.row_span = area.rows.len().max(1);
+                    cell.column_span = area.columns.len().max(1);
+                    occupy(&mut occupied, row, column, cell.row_span, cell.column_span);
+                    row_count = row_count.max(row + cell.row_span);
+                    column_count = column_count.max(column + cell.column_span);
+                    slots.push(Slot {
+                        cell,
+                        part,
+                        row,
+                        column,
+                    });
+                    None
+                })
+                .collect()
+        })
+        .collect();
+    slots.reserve(rows.iter().map(Vec::len).sum());
+    occupied.reserve(rows.len().saturating_sub(occupied.len()));
+    for (row, cells) in rows.into_iter().enumerate() {
+        row_count = row_count.max(row + 1);
+        let mut column = 0;
+        for cell in cells {
+            let (row_span, column_span) = cell
+                .as_ref()
+                .map_or((1, 1), |(_, cell)| (cell.row_span, cell.column_span));
+            let backfill = dense && matches!(cell, Some((Part::Body(..), _)));
+            if backfill {
+                column = 0;
+            }
+            let hole = (header_rows..row).filter(|_| backfill).find_map(|earlier| {
+                (0..(column_count + 1).saturating_sub(column_span))
+                    .find(|&column| is_free(&occupied, earlier, column, row_span, column_span))
+                    .map(|column| (earlier, column))
+            });
+            let (cell_row, cell_column) = hole.unwrap_or_else(|| {
+                while !is_free(&occupied, row, column, row_span, column_span) {
+                    column += 1;
+                }
+                column += column_span;
+                (row, column - column_span)
+            });
+            occupy(&mut occupied, cell_row, cell_column, row_span, column_span);
+            if let Some((part, cell)) = cell {
+                slots.push(Slot {
+                    cell,
+                    part,
+                    row: cell_row,
+                    column: cell_column,
+                });
+            }
+            row_count = row_count.max(cell_row + row_span);
+            column_count = column_count.max(cell_column + column_span);
+        }
+    }
+    (slots, row_count, column_count)
+}
+
+/// Mark a block of slots with its top-left corner at the given position as occupied.
+fn occupy(
+    occupied: &mut Vec<Vec<bool>>,
+    row: usize,
+    column: usize,
+    row_span: usize,
+    column_span: usize,
+) {
+    if occupied.len() < row + row_span {
+        occupied.resize_with(row + row_span, Vec::new);
+    }
+    for occupied_row in &mut occupied[row..row + row_span] {
+        if occupied_row.len() < column + column_span {
+            occupied_row.resize(column + column_span, false);
+        }
+        occupied_row[column..column + column_span].fill(true);
+    }
+}
+
+/// Check whether a block of slots with its top-left corner at the given position is unoccupied.
+fn is_free(
+    occupied: &[Vec<bool>],
+    row: usize,
+    column: usize,
+    row_span: usize,
+    column_span: usize,
+) -> bool {
+    occupied
+        .iter()
+        .skip(row)
+        .take(row_span)
+        .all(|occupied_row| {
+            occupied_row
+                .iter()
+                .skip(column)
+                .take(column_span)
+                .all(|occupied| !occupied)
+        })
+}
+
+/// Resolve the lengths of a set of tracks into sizes. Fixed tracks keep their size, shrinking tracks
+/// take the size of their largest content, and filling tracks share whatever space is left over.
+/// Every track is kept within its smallest and largest size, and the space a filling track cannot
+/// take because of its limits is shared between the other filling tracks.
+///
+/// If there is no limit on the available space, filling tracks shrink to their content instead.
+fn resolve(lengths: &[Length], limits: &[(f32, f32)], content: &[f32], available: f32) -> Vec<f32> {
+    let limit = |size: f32, (min, max): (f32, f32)| size.min(max).max(min);
+    let mut sizes: Vec<f32> = lengths
+        .iter()
+        .zip(limits)
+        .zip(content)
+        .map(|((length, &limits), content)| match length {
+            Length::Fixed(size) => limit(*size, limits),
+            Length::Shrink => limit(*content, limits),
+            Length::Fill | Length::FillPortion(_) => 0.0,
+        })
+        .collect();
+    let mut filling: Vec<bool> = lengths
+        .iter()
+        .map(|length| length.fill_factor() > 0)
+        .collect();
+    loop {
+        let portions: u32 = lengths
+            .iter()
+            .zip(&filling)
+            .filter(|(_, filling)| **filling)
+            .map(|(length, _)| u32::from(length.fill_factor()))
+            .sum();
+        if portions == 0 {
+            break;
+        }
+        let taken: f32 = sizes
+            .iter()
+            .zip(&filling)
+            .filter(|(_, filling)| !**filling)
+            .map(|(size, _)| size)
+            .sum();
+        let remaining = (available - taken).max(0.0);
+        let mut limited = false;
+        for (track, length) in lengths.iter().enumerate() {
+            if !filling[track] {
+                continue;
+            }
+            let share = if remaining.is_finite() {
+                #[allow(clippy::cast_precision_loss)]
+                let share = remaining * f32::from(length.fill_factor()) / portions as f32;
+                share
+            } else {
+                content[track]
+            };
+            sizes[track] = limit(share, limits[track]);
+            let (min, max) = limits[track];
+            if remaining.is_finite() && !(min..=max).contains(&share) {
+                filling[track] = false;
+                limited = true;
+            }
+        }
+        if !limited {
+            break;
+        }
+    }
+    sizes
+}
+
+/// Check whether the size of any of the given tracks depends on its content or the available space.
+fn is_flexible(lengths: &[Length]) -> bool {
+    lengths
+        .iter()
+        .any(|length| !matches!(length, Length::Fixed(_)))
+}
+
+/// Check whether the content of a cell spanning the given tracks needs to be measured. Cells in a
+/// single track are measured unless the track is fixed, while cells spanning several tracks are
+/// only measured if one of them shrinks to its content.
+fn is_measured(lengths: &[Length]) -> bool {
+    match lengths {
+        [length] => !matches!(length, Length::Fixed(_)),
+        lengths => lengths.contains(&Length::Shrink),
+    }
+}
+
+/// Grow the content sizes of the shrinking tracks under a cell spanning several tracks evenly, so
+/// that together with the other tracks and the gutters between them they fit the cell.
+fn spread(lengths: &[Length], content: &mut [f32], tracks: Range<usize>, size: f32, gutter: f32) {
+    let current = tracks
+        .clone()
+        .map(|track| match lengths[track] {
+            Length::Fixed(size) => size,
+            _ => content[track],
+        })
+        .sum::<f32>()
+        + gutters(tracks.len(), gutter);
+    let shrinking: Vec<usize> = tracks
+        .filter(|&track| lengths[track] == Length::Shrink)
+        .collect();
+    if size > current && !shrinking.is_empty() {
+        #[allow(clippy::cast_precision_loss)]
+        let extra = (size - current) / shrinking.len() as f32;
+        for track in shrinking {
+            content[track] += extra;
+        }
+    }
+}
+
+/// Find the total length of the gutters between the given number of tracks.
+fn gutters(count: usize, gutter: f32) -> f32 {
+    #[allow(clippy::cast_precision_loss)]
+    let count = count.saturating_sub(1) as f32;
+    gutter * count
+}
+
+/// Find the offset of the start of each track, along with the total length of the tracks and the
+/// gutters between them.
+fn offsets(sizes: &[f32], gutter: f32, padding: f32) -> (Vec<f32>, f32) {
+    let mut offsets = Vec::with_capacity(sizes.len());
+    let mut offset = padding;
+    for (index, size) in sizes.iter().enumerate() {
+        if index > 0 {
+            offset += gutter;
+        }
+        offsets.push(offset);
+        offset += size;
+    }
+    (offsets, offset - padding)
+}
+
+/// Move the tracks at the given offsets to spread them across the given space left over after
+/// them. Returns how much the space between each pair of tracks grows by.
+fn distribute(offsets: &mut [f32], leftover: f32, distribution: Distribution) -> f32 {
+    let leftover = leftover.max(0.0);
+    #[allow(clippy::cast_precision_loss)]
+    let count = offsets.len() as f32;
+    let (start, gap) = match distribution {
+        Distribution::Center => (leftover / 2.0, 0.0),
+        Distribution::End => (leftover, 0.0),
+        Distribution::SpaceBetween if offsets.len() > 1 => (0.0, leftover / (count - 1.0)),
+        Distribution::SpaceAround if !offsets.is_empty() => {
+            (leftover / count / 2.0, leftover / count)
+        }
+        Distribution::Start | Distribution::SpaceBetween | Distribution::SpaceAround => (0.0, 0.0),
+    };
+    for (index, offset) in offsets.iter_mut().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let index = index as f32;
+        *offset += gap.mul_add(index, start);
+    }
+    gap
+}
+
+/// Find the length of a span of tracks starting at the given track, including the gutters
+/// between them.
+fn span_length(sizes: &[f32], start: usize, span: usize, gutter: f32) -> f32 {
+    sizes[start..start + span].iter().sum::<f32>() + gutters(span, gutter)
+}
+
+/// Pair up the offsets and sizes of a set of tracks.
+fn tracks(offsets: &[f32], sizes: &[f32]) -> Vec<Track> {
+    offsets
+        .iter()
+        .zip(sizes)
+        .map(|(&offset, &size)| Track { offset, size })
+        .collect()
+}
+
+/// Find the extent of a line running across the given track, reaching halfway into the gutters on
+/// either side of it so that it meets the lines running across the tracks next to it.
+fn line_extent(tracks: &[Track], index: usize, gutter: f32) -> (f32, f32) {
+    let track = tracks[index];
+    let start = if index == 0 {
+        track.offset
+    } else {
+        track.offset - gutter / 2.0
+    };
+    let end = if index + 1 == tracks.len() {
+        track.end()
+    } else {
+        track.end() + gutter / 2.0
+    };
+    (start, end)
+}
+
+/// Resolve the length of a grid along one axis from the lengths of its tracks.
+fn fill_or_shrink(lengths: &[Length]) -> Length {
+    if lengths.iter().any(|length| length.fill_factor() > 0) {
+        Length::Fill
+    } else {
+        Length::Shrink
+    }
+}
+
+impl<'a, M, T, R: advanced::Renderer> Instance<'a, M, T, R> {
+    /// Mirror the columns of the grid if it is laid out from right to left, along with the
+    /// horizontal alignment of its cells.
+    pub fn mirrored(mut self, right_to_left: bool) -> Self {
+        if !right_to_left {
+            return self;
+        }
+        let column_count = self.column_widths.len();
+        for slot in &mut self.slots {
+            slot.column = column_count - slot.column - slot.cell.column_span;
+            slot.cell.align_x = match slot.cell.align_x {
+                Horizontal::Left => Horizontal::Right,
+                Horizontal::Center => Horizontal::Center,
+                Horizontal::Right => Horizontal::Left,
+            };
+        }
+        self.column_widths.reverse();
+        self.column_limits.reverse();
+        self.right_to_left = true;
+        self
+    }
+
+    /// Find the tracks the columns of the grid are shown in, leaving out the column of row
+    /// numbers, which is on the left unless the grid is laid out from right to left.
+    const fn column_tracks(&self) -> Range<usize> {
+        let start = if self.right_to_left {
+            0
+        } else {
+            self.number_columns
+        };
+        start..start + self.column_widths.len() - self.number_columns
+    }
+
+    /// Convert between the index of a column and the position of the column among the columns
+    /// shown, from left to right, which are only different if the grid is laid out from right to
+    /// left.
+    const fn column_position(&self, column: usize) -> usize {
+        if self.right_to_left {
+            self.column_widths.len() - self.number_columns - 1 - column
+        } else {
+            column
+        }
+    }
+
+    /// Draw the frame behind the grid within the given bounds, if the grid has a frame.
+    fn draw_frame(&self, renderer: &mut R, theme: &T, bounds: Rectangle) {
+        let Some(frame_style) = &self.frame_style else {
+            return;
+        };
+        let mut frame = frame_style(theme);
+        if self.corner_radius > 0.0 {
+            frame.border.radius = self.corner_radius.into();
+        }
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds,
+                border: frame.border,
+                shadow: frame.shadow,
+            },
+            frame
+                .background
+                .unwrap_or(Background::Color(Color::TRANSPARENT)),
+        );
+    }
+
+    /// Round the corners of the cell in the given slot that are outer corners of the grid with the
+    /// corner radius of the grid, if it has one.
+    fn round_corners(&self, state: &State, slot: &Slot<'_, M, T, R>, radius: &mut Radius) {
+        if self.corner_radius <= 0.0 {
+            return;
+        }
+        let top = slot.row == 0;
+        let left = slot.column == 0;
+        let bottom = slot.row + slot.cell.row_span >= state.rows.len();
+        let right = slot.column + slot.cell.column_span >= state.columns.len();
+        for (corner, outer) in [
+            (&mut radius.top_left, top && left),
+            (&mut radius.top_right, top && right),
+            (&mut radius.bottom_right, bottom && right),
+            (&mut radius.bottom_left, bottom && left),
+        ] {
+            if outer {
+                *corner = self.corner_radius;
+            }
+        }
+    }
+
+    /// Find the part of the grid the cell under the cursor belongs to, if the cursor is over a cell.
+    fn part_at(&self, state: &State, layout: Layout<'_>, cursor: mouse::Cursor) -> Option<Part> {
+        self.slot_at(state, layout, cursor).map(|slot| slot.part)
+    }
+
+    /// Find the slot of the cell under the cursor, if the cursor is over a cell.
+    fn slot_at(
+        &self,
+        state: &State,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+    ) -> Option<&Slot<'a, M, T, R>> {
+        self.slots
+            .iter()
+            .zip(layout.children())
+            .find(|(slot, child)| {
+                self.clip_slot(state, cursor, layout, slot)
+                    .is_over(child.bounds())
+            })
+            .map(|(slot, _)| slot)
+    }
+
+    /// Find how pressing the cell at the given row and column with the given modifier keys held
+    /// changes the selection.
+    fn select(
+        &self,
+        modifiers: keyboard::Modifiers,
+        row: usize,
+        column: usize,
+    ) -> SelectionChanged {
+        let mut selection = self.selection.clone();
+        selection.extend(self.selected);
+        match (modifiers.command(), modifiers.shift(), self.selected) {
+            (extend, true, Some((anchor_row, anchor_column))) => {
+                if !extend {
+                    selection.clear();
+                }
+                selection.extend(self.slots.iter().filter_map(|slot| {
+                    match slot.part {
+                        Part::Body(slot_row, slot_column)
+                            if (anchor_row.min(row)..=anchor_row.max(row)).contains(&slot_row)
+                                && (anchor_column.min(column)..=anchor_column.max(column))
+                                    .contains(&slot_column) =>
+                        {
+                            Some((slot_row, slot_column))
+                        }
+                        _ => None,
+                    }
+                }));
+                SelectionChanged {
+                    selection,
+                    anchor: self.selected,
+                }
+            }
+            (true, _, _) => {
+                if !selection.remove(&(row, column)) {
+                    selection.insert((row, column));
+                }
+                SelectionChanged {
+                    selection,
+                    anchor: Some((row, column)),
+                }
+            }
+            _ => SelectionChanged {
+                selection: HashSet::from([(row, column)]),
+                anchor: Some((row, column)),
+            },
+        }
+    }
+
+    /// Check whether the selection can be changed or cells can be pressed, so the grid can be
+    /// focused to move between its cells with the keyboard.
+    fn is_navigable(&self) -> bool {
+        self.on_focus_cell.is_some()
+            || self.on_select.is_some()
+            || self.on_selection_change.is_some()
+            || self.on_press.is_some()
+            || self.slots.iter().any(|slot| slot.cell.on_press.is_some())
+    }
+
+    /// Find the focused cell, which is the selected cell if there is one, and otherwise the cell
+    /// moved to with the keyboard or pressed last.
+    fn focused_cell(&self, state: &State) -> Option<(usize, usize)> {
+        self.selected.or(state.focused_cell)
+    }
+
+    /// Find how many cells each of the rows of the grid has, in the order the rows are laid out.
+    fn row_lengths(&self) -> Vec<usize> {
+        let mut lengths = vec![0; self.body_rows.len()];
+        for slot in &self.slots {
+            if let Part::Body(_, column) = slot.part
+                && let Some(length) = lengths.get_mut(slot.row - self.header_rows)
+            {
+                *length = (*length).max(column + 1);
+            }
+        }
+        lengths
+    }
+
+    /// Produce the messages for selecting the cell at the given row and column with the given
+    /// modifier keys held, for the parts of the selection that change.
+    fn select_cell(
+        &self,
+        modifiers: keyboard::Modifiers,
+        row: usize,
+        column: usize,
+        shell: &mut Shell<'_, M>,
+    ) {
+        if let Some(on_select) = &self.on_select
+            && self.selected != Some((row, column))
+        {
+            shell.publish(on_select(row, column));
+        }
+        if let Some(on_selection_change) = &self.on_selection_change {
+            let changed = self.select(modifiers, row, column);
+            let mut selection = self.selection.clone();
+            selection.extend(self.selected);
+            if changed.selection != selection || changed.anchor != self.selected {
+                shell.publish(on_selection_change(changed));
+            }
+        }
+    }
+
+    /// Find the cell that pressing the given key moves to from the focused cell, if it moves at
+    /// all. Without a focused cell, any key moves to the first cell.
+    ///
+    /// The arrow keys move by one cell, Home and End move to the ends of the row, and Page Up and
+    /// Page Down move by as many rows as fit in the viewport.
+    fn navigate(
+        &self,
+        state: &State,
+        key: keyboard::key::Named,
+        viewport: &Rectangle,
+    ) -> Option<(usize, usize)> {
+        use keyboard::key::Named;
+
+        let lengths = self.row_lengths();
+        let last_position = lengths.len().checked_sub(1)?;
+        let first = || {
+            lengths
+                .iter()
+                .position(|&length| length > 0)
+                .map(|position| (self.body_rows[position], 0))
+        };
+        let Some((row, column)) = self.focused_cell(state) else {
+            return first();
+        };
+        let Some(position) = self.body_rows.iter().position(|&body_row| body_row == row) else {
+            return first();
+        };
+
+        let body = state
+            .rows
+            .iter()
+            .skip(self.header_rows)
+            .take(self.body_rows.len());
+        #[allow(clippy::cast_precision_loss)]
+        let average =
+            body.clone().map(|track| track.size).sum::<f32>() / body.count().max(1) as f32;
+        let pitch = average + self.row_gutter;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let page = if pitch > 0.0 {
+            ((viewport.height / pitch).floor() as usize).max(1)
+        } else {
+            1
+        };
+
+        let target_position = match key {
+            Named::ArrowUp => position.checked_sub(1)?,
+            Named::ArrowDown => position + 1,
+            Named::PageUp => position.saturating_sub(page),
+            Named::PageDown => position.saturating_add(page).min(last_position),
+            _ => position,
+        };
+        let length = *lengths.get(target_position)?;
+        let target_column = match key {
+            Named::ArrowLeft if self.right_to_left => column + 1,
+            Named::ArrowRight if self.right_to_left => column.checked_sub(1)?,
+            Named::ArrowLeft => column.checked_sub(1)?,
+            Named::ArrowRight => column + 1,
+            Named::Home => 0,
+            Named::End => length.checked_sub(1)?,
+            Named::ArrowUp | Named::ArrowDown | Named::PageUp | Named::PageDown => {
+                column.min(length.checked_sub(1)?)
+            }
+            _ => return None,
+        };
+        let target = (self.body_rows[target_position], target_column);
+        (target_column < length && target != (row, column)).then_some(target)
+    }
+
+    /// Find the cell that pressing Tab moves to from the focused cell, or Shift+Tab if going
+    /// backward, if there is one before the end of the grid. Without a focused cell, Tab moves to
+    /// the first cell and Shift+Tab to the last cell.
+    fn traverse(&self, state: &State, backward: bool) -> Option<(usize, usize)> {
+        let lengths = self.row_lengths();
+        let cells: Vec<_> = lengths
+            .iter()
+            .enumerate()
+            .flat_map(|(position, &length)| (0..length).map(move |column| (position, column)))
+            .collect();
+        let cell = |(position, column): (usize, usize)| (self.body_rows[position], column);
+        let Some((row, column)) = self.focused_cell(state) else {
+            return if backward {
+                cells.last()
+            } else {
+                cells.first()
+            }
+            .copied()
+            .map(cell);
+        };
+        let position = self
+            .body_rows
+            .iter()
+            .position(|&body_row| body_row == row)?;
+        match self.tab_traversal {
+            Traversal::Cell => {
+                let index = cells
+                    .iter()
+                    .position(|&entry| entry == (position, column))?;
+                let target = if backward {
+                    index.checked_sub(1)?
+                } else {
+                    index + 1
+                };
+                cells.get(target).copied().map(cell)
+            }
+            Traversal::Row => {
+                let mut positions = (0..lengths.len()).filter(|&target| lengths[target] > 0);
+                let target = if backward {
+                    positions.rfind(|&target| target < position)?
+                } else {
+                    positions.find(|&target| target > position)?
+                };
+                Some(cell((target, column.min(lengths[target] - 1))))
+            }
+        }
+    }
+
+    /// Publish the messages for pressing the focused cell, returning whether there were any.
+    fn press_focused(&self, state: &State, shell: &mut Shell<'_, M>) -> bool {
+        let Some((row, column)) = self.focused_cell(state) else {
+            return false;
+        };
+        let cell_pressed = self
+            .slots
+            .iter()
+            .find(|slot| slot.part == Part::Body(row, column))
+            .and_then(|slot| slot.cell.on_press.as_ref())
+            .map(|on_press| shell.publish(on_press()))
+            .is_some();
+        let grid_pressed = self
+            .on_press
+            .as_ref()
+            .map(|on_press| shell.publish(on_press(row, column)))
+            .is_some();
+        cell_pressed || grid_pressed
+    }
+
+    /// Handle a key being pressed while the grid is focused, moving between cells with the
+    /// navigation keys and Tab, pressing the focused cell with Enter, and copying and pasting cells
+    /// with the clipboard shortcuts.
+    fn key_pressed(
+        &self,
+        state: &mut State,
+        key: &keyboard::Key,
+        modifiers: keyboard::Modifiers,
+        clipboard: &dyn Clipboard,
+        shell: &mut Shell<'_, M>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        match key {
+            keyboard::Key::Character(key)
+                if modifiers.command() && self.use_clipboard(key, clipboard, shell) =>
+            {
+                event::Status::Captured
+            }
+            keyboard::Key::Named(keyboard::key::Named::Enter) => {
+                if self.press_focused(state, shell) {
+                    event::Status::Captured
+                } else {
+                    event::Status::Ignored
+                }
+            }
+            keyboard::Key::Named(key) => {
+                let target = if *key == keyboard::key::Named::Tab {
+                    self.traverse(state, modifiers.shift())
+                } else {
+                    self.navigate(state, *key, viewport)
+                };
+                let Some((row, column)) = target else {
+                    return event::Status::Ignored;
+                };
+                state.focused_cell = Some((row, column));
+                if let Some(on_focus_cell) = &self.on_focus_cell {
+                    shell.publish(on_focus_cell(row, column));
+                }
+                self.select_cell(keyboard::Modifiers::default(), row, column, shell);
+                event::Status::Captured
+            }
+            _ => event::Status::Ignored,
+        }
+    }
+
+    /// Ask the grid to copy the text of the selected cells to the clipboard if the given key is C,
+    /// or paste the text in the clipboard into the grid if it is V. Returns whether the clipboard
+    /// was used.
+    fn use_clipboard(
+        &self,
+        key: &str,
+        clipboard: &dyn Clipboard,
+        shell: &mut Shell<'_, M>,
+    ) -> bool {
+        match key {
+            "c" => {
+                if self.selected.is_none() && self.selection.is_empty() {
+                    return false;
+                }
+                self.copying.set(true);
+                true
+            }
+            "v" => {
+                if let Some(on_paste) = &self.on_paste
+                    && let Some((row, column)) = self.selected
+                    && let Some(text) = clipboard.read(clipboard::Kind::Standard)
+                {
+                    shell.publish(on_paste(row, column, parse_records(&text, '\t')));
+                    return true;
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Find the widths of the columns of the grid, measuring the contents of the columns that are
+    /// not fixed. A column being resized is as wide as it has been dragged to be.
+    fn measure_columns(&self, tree: &mut Tree, renderer: &R, available: Size) -> Vec<f32> {
+        let mut lengths = self.column_widths.clone();
+        if let Some(resize) = tree.state.downcast_ref::<State>().resize
+            && let Some(length) = lengths.get_mut(resize.column)
+        {
+            *length = Length::Fixed(resize.width);
+        }
+        let mut content_widths = vec![0.0f32; lengths.len()];
+        let mut spanning = Vec::new();
+        if is_flexible(&lengths) {
+            for (slot, tree) in self.slots.iter().zip(&mut tree.children) {
+                let span = slot.cell.column_span;
+                if !is_measured(&lengths[slot.column..slot.column + span]) {
+                    continue;
+                }
+                let node = slot.cell.element.as_widget().layout(
+                    tree,
+                    renderer,
+                    &layout::Limits::new(Size::ZERO, available),
+                );
+                if span == 1 {
+                    content_widths[slot.column] =
+                        content_widths[slot.column].max(node.size().width);
+                } else {
+                    spanning.push((slot.column, span, node.size().width));
+                }
+            }
+        }
+        for (start, span, width) in spanning {
+            spread(
+                &lengths,
+                &mut content_widths,
+                start..start + span,
+                width,
+                self.column_gutter,
+            );
+        }
+        resolve(
+            &lengths,
+            &self.column_limits,
+            &content_widths,
+            available.width,
+        )
+    }
+
+    /// Find the heights of the rows of the grid, measuring the contents of the rows that are not
+    /// fixed at the widths their columns were given. If the cells keep an aspect ratio, the rows
+    /// of the body are as tall as the widest column makes them instead.
+    fn measure_rows(
+        &self,
+        tree: &mut Tree,
+        renderer: &R,
+        available: Size,
+        column_widths: &[f32],
+    ) -> Vec<f32> {
+        let mut lengths = self.row_heights.clone();
+        if let Some(aspect_ratio) = self.aspect_ratio {
+            let width = column_widths.iter().copied().fold(0.0, f32::max);
+            let end = self.body_end();
+            for length in lengths.iter_mut().take(end).skip(self.header_rows) {
+                *length = Length::Fixed(width / aspect_ratio);
+            }
+        }
+        let mut content_heights = vec![0.0f32; lengths.len()];
+        let mut spanning = Vec::new();
+        if is_flexible(&lengths) {
+            for (slot, tree) in self.slots.iter().zip(&mut tree.children) {
+                let span = slot.cell.row_span;
+                if !is_measured(&lengths[slot.row..slot.row + span]) {
+                    continue;
+                }
+                let width = span_length(
+                    column_widths,
+                    slot.column,
+                    slot.cell.column_span,
+                    self.column_gutter,
+                );
+                let node = slot.cell.element.as_widget().layout(
+                    tree,
+                    renderer,
+                    &layout::Limits::new(Size::ZERO, Size::new(width, available.height)),
+                );
+                if span == 1 {
+                    content_heights[slot.row] = content_heights[slot.row].max(node.size().height);
+                } else {
+                    spanning.push((slot.row, span, node.size().height));
+                }
+            }
+        }
+        for (start, span, height) in spanning {
+            spread(
+                &lengths,
+                &mut content_heights,
+                start..start + span,
+                height,
+                self.row_gutter,
+            );
+        }
+        resolve(
+            &lengths,
+            &self.row_limits,
+            &content_heights,
+            available.height,
+        )
+    }
+
+    /// Check whether the grid scrolls its rows within its bounds.
+    const fn scrolls(&self) -> bool {
+        self.scrollable || self.viewport_height.is_some()
+    }
+
+    /// Find the end of the rows of the body, which are followed by the footer and the pager.
+    const fn body_end(&self) -> usize {
+        self.row_heights
+            .len()
+            .saturating_sub(self.footer_rows + self.pager_rows)
+    }
+
+    /// Find the rows in the given band of rows. The pager is part of the footer if there is one,
+    /// and part of the body if not.
+    const fn band_rows(&self, band: Band) -> Range<usize> {
+        let footer = if self.footer_rows > 0 {
+            self.body_end()
+        } else {
+            self.row_heights.len()
+        };
+        match band {
+            Band::Header => 0..self.header_rows,
+            Band::Body => self.header_rows..footer,
+            Band::Footer => footer..self.row_heights.len(),
+        }
+    }
+
+    /// Find the band of rows the cell in the given slot is in.
+    fn band(&self, slot: &Slot<'_, M, T, R>) -> Band {
+        [Band::Header, Band::Footer]
+            .into_iter()
+            .find(|&band| self.band_rows(band).contains(&slot.row))
+            .unwrap_or(Band::Body)
+    }
+
+    /// Find the area of the grid that the rows in the given band in the frozen or scrolling
+    /// columns are visible in. The header and footer stay in place while the body scrolls
+    /// vertically and the frozen columns stay in place while the others scroll horizontally.
+    fn area(&self, state: &State, bounds: Rectangle, band: Band, frozen: bool) -> Rectangle {
+        let split = |tracks: &[Track], count: usize| {
+            count
+                .checked_sub(1)
+                .and_then(|track| tracks.get(track))
+                .copied()
+                .map_or(0.0, Track::end)
+        };
+        let left = split(&state.columns, self.frozen_columns).min(bounds.width);
+        let top = split(&state.rows, self.header_rows).min(bounds.height);
+        let bottom = state
+            .rows
+            .get(self.band_rows(Band::Footer).start)
+            .map_or(bounds.height, |track| track.offset)
+            .clamp(top, bounds.height.max(top));
+        let (x, width) = if frozen {
+            (bounds.x, left)
+        } else {
+            (bounds.x + left, bounds.width - left)
+        };
+        let (y, height) = match band {
+            Band::Header => (bounds.y, top),
+            Band::Body => (bounds.y + top, bottom - top),
+            Band::Footer => (bounds.y + bottom, bounds.height - bottom),
+        };
+        Rectangle::new(Point::new(x, y), Size::new(width, height))
+    }
+
+    /// Find the area of the grid that the cell in the given slot is visible in.
+    fn slot_area(&self, state: &State, bounds: Rectangle, slot: &Slot<'_, M, T, R>) -> Rectangle {
+        self.area(
+            state,
+            bounds,
+            self.band(slot),
+            slot.column < self.frozen_columns,
+        )
+    }
+
+    /// Check whether each of the elements of the grid, in the order of the children of its layout,
+    /// can be seen, so the overlays of cells scrolled out of view, like the menu of a pick list,
+    /// are not shown. Every element of a grid that does not scroll can be seen.
+    fn visible(&self, state: &State, layout: Layout<'_>) -> Vec<bool> {
+        let bounds = layout.bounds();
+        layout
+            .children()
+            .enumerate()
+            .map(|(index, child)| {
+                let area = self
+                    .slots
+                    .get(index)
+                    .map_or(bounds, |slot| self.slot_area(state, bounds, slot));
+                !self.scrolls() || child.bounds().intersects(&area)
+            })
+            .collect()
+    }
+
+    /// Scroll the tracks of the grid, which is of the given size and has content of the given size,
+    /// keeping the header and the frozen columns in place and the footer at the bottom. The grid
+    /// is scrolled to the cell it should be scrolled to first, if there is one.
+    fn scroll(&self, state: &mut State, content: Size, size: Size) {
+        state.max_scroll = Vector::new(
+            (content.width - size.width).max(0.0),
+            (content.height - size.height).max(0.0),
+        );
+        if let Some(cell) = state.scroll_target.take() {
+            let target = self.reveal(state.scroll, cell, &state.columns, &state.rows, size);
+            let target = Vector::new(
+                target.x.min(state.max_scroll.x),
+                target.y.min(state.max_scroll.y),
+            );
+            if self.scroll_animation.is_some() {
+                state.scroll_animation = Some(ScrollAnimation {
+                    from: state.scroll,
+                    to: target,
+                    start: Instant::now(),
+                });
+            } else {
+                state.scroll = target;
+            }
+        }
+        state.scroll = Vector::new(
+            state.scroll.x.min(state.max_scroll.x),
+            state.scroll.y.min(state.max_scroll.y),
+        );
+        for track in state.columns.iter_mut().skip(self.frozen_columns) {
+            track.offset -= state.scroll.x;
+        }
+        let footer = self.band_rows(Band::Footer);
+        for (row, track) in state.rows.iter_mut().enumerate().skip(self.header_rows) {
+            track.offset -= if footer.contains(&row) {
+                state.max_scroll.y
+            } else {
+                state.scroll.y
+            };
+        }
+    }
+
+    /// Find the identity of the row at the given index, which tells it apart from the other rows
+    /// of the grid from one layout to the next.
+    fn row_id(&self, row: usize) -> u64 {
+        self.row_keys
+            .get(row)
+            .copied()
+            .flatten()
+            .unwrap_or(row as u64)
+    }
+
+    /// Start animating the rows of the body that were added or moved since the grid was last laid
+    /// out, remembering where each row is for the next time. Nothing is animated the first time
+    /// the grid is laid out.
+    fn transition_rows(&self, state: &mut State) {
+        let Some((transition, _)) = self.row_transition else {
+            state.row_offsets = None;
+            state.row_animations.clear();
+            return;
+        };
+        let now = Instant::now();
+        let body = self
+            .body_rows
+            .iter()
+            .zip(state.rows.iter().skip(self.header_rows));
+        if let Some(previous) = &state.row_offsets {
+            for (&row, track) in body.clone() {
+                let id = self.row_id(row);
+                let start = match (previous.get(&id), transition) {
+                    (None, RowTransition::Fade) => Some((0.0, true)),
+                    (None, RowTransition::Slide) => Some((-track.size, true)),
+                    (Some(&offset), RowTransition::Slide)
+                        if (offset - track.offset).abs() > f32::EPSILON =>
+                    {
+                        let shown = state.row_animations.get(&id).map_or(0.0, |animation| {
+                            animation.offset * (1.0 - ease(animation.progress))
+                        });
+                        Some((offset - track.offset + shown, false))
+                    }
+                    _ => None,
+                };
+                if let Some((offset, appearing)) = start {
+                    state.row_animations.insert(
+                        id,
+                        RowAnimation {
+                            start: now,
+                            offset,
+                            appearing,
+                            progress: 0.0,
+                        },
+                    );
+                }
+            }
+        }
+        let offsets = body
+            .map(|(&row, track)| (self.row_id(row), track.offset))
+            .collect();
+        state.row_offsets = Some(offsets);
+    }
+
+    /// Find the style the cell in the given slot is drawn with, taking whether it is hovered, how
+    /// its row is being animated and which of its corners are rounded into account.
+    fn cell_style(
+        &self,
+        state: &State,
+        theme: &T,
+        slot: &Slot<'_, M, T, R>,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+        transition: Option<(f32, f32)>,
+    ) -> container::Style {
+        let mut cell_style = (slot.cell.style)(theme);
+        if let Some(hovered_style) = &slot.cell.hovered_style
+            && cursor.is_over(bounds)
+        {
+            cell_style = layer(&cell_style, hovered_style);
+        }
+        if let Some((alpha, _)) = transition {
+            cell_style = faded(&cell_style, alpha);
+        }
+        self.round_corners(state, slot, &mut cell_style.border.radius);
+        cell_style
+    }
+
+    /// Find how opaque the cell in the given slot is drawn and how far down it is moved while its
+    /// row is being animated, if it is.
+    fn transition(&self, state: &State, slot: &Slot<'_, M, T, R>) -> Option<(f32, f32)> {
+        let (transition, _) = self.row_transition?;
+        let Part::Body(row, _) = slot.part else {
+            return None;
+        };
+        let animation = state.row_animations.get(&self.row_id(row))?;
+        let progress = ease(animation.progress);
+        let alpha = if animation.appearing && transition == RowTransition::Fade {
+            progress
+        } else {
+            1.0
+        };
+        Some((alpha, animation.offset * (1.0 - progress)))
+    }
+
+    /// Move the animations of the grid on to the time of the given event if it is a redraw
+    /// request, asking for another redraw until they are done.
+    fn animate(&self, state: &mut State, event: &Event, shell: &mut Shell<'_, M>) {
+        let Event::Window(window::Event::RedrawRequested(now)) = *event else {
+            return;
+        };
+        if let Some(animation) = state.scroll_animation {
+            let progress = self
+                .scroll_animation
+                .map_or(1.0, |duration| progress(animation.start, now, duration));
+            state.scroll = animation.from + (animation.to - animation.from) * ease(progress);
+            state.scroll_animation = Some(animation).filter(|_| progress < 1.0);
+            shell.invalidate_layout();
+        }
+        if let Some((_, duration)) = self.row_transition {
+            for animation in state.row_animations.values_mut() {
+                animation.progress = progress(animation.start, now, duration);
+            }
+            state
+                .row_animations
+                .retain(|_, animation| animation.progress < 1.0);
+        }
+        if state.scroll_animation.is_some() || !state.row_animations.is_empty() {
+            shell.request_redraw(window::RedrawRequest::NextFrame);
+        }
+    }
+
+    /// Ask for the rows that are not loaded yet to be loaded once the first of them is less than a
+    /// viewport away from coming into view, unless they were already asked for.
+    fn request_rows(&self, state: &mut State, layout: Layout<'_>, shell: &mut Shell<'_, M>) {
+        let Some(on_request_rows) = &self.on_request_rows else {
+            return;
+        };
+        if self.loading_rows.is_empty() || state.requested_rows.as_ref() == Some(&self.loading_rows)
+        {
+            return;
+        }
+        let Some(track) = self
+            .body_rows
+            .iter()
+            .position(|row| self.loading_rows.contains(row))
+            .and_then(|index| state.rows.get(self.header_rows + index))
+        else {
+            return;
+        };
+        if self.scrolls() && track.offset > 2.0 * layout.bounds().height {
+            return;
+        }
+        state.requested_rows = Some(self.loading_rows.clone());
+        shell.publish(on_request_rows(self.loading_rows.clone()));
+    }
+
+    /// Find how far to scroll the grid from the given scroll offset so that the cell at the given
+    /// row and column is in view, given the tracks of the grid before it is scrolled and its
+    /// size. The grid is scrolled as little as possible, and not at all along an axis the cell
+    /// is already in view along, or if the cell is in the header, in a frozen column, or not on
+    /// the page being shown.
+    fn reveal(
+        &self,
+        scroll: Vector,
+        (row, column): (usize, usize),
+        columns: &[Track],
+        rows: &[Track],
+        size: Size,
+    ) -> Vector {
+        let reveal = |scroll: f32, track: Track, start: f32, end: f32| {
+            if track.offset - scroll < start {
+                track.offset - start
+            } else if track.end() - scroll > end {
+                (track.end() - end).min(track.offset - start)
+            } else {
+                scroll
+            }
+            .max(0.0)
+        };
+        let start = |tracks: &[Track], count: usize, gutter: f32| {
+            count
+                .checked_sub(1)
+                .and_then(|track| tracks.get(track))
+                .map_or(self.padding, |track| track.end() + gutter)
+        };
+        let mut scroll = scroll;
+        if let Some(position) = self.body_rows.iter().position(|&body_row| body_row == row)
+            && let Some(&track) = rows.get(self.header_rows + position)
+        {
+            let top = start(rows, self.header_rows, self.row_gutter);
+            scroll.y = reveal(scroll.y, track, top, size.height - self.padding);
+        }
+        if column < self.column_tracks().len() {
+            let index = self.column_tracks().start + self.column_position(column);
+            if index >= self.frozen_columns
+                && let Some(&track) = columns.get(index)
+            {
+                let left = start(columns, self.frozen_columns, self.column_gutter);
+                scroll.x = reveal(scroll.x, track, left, size.width - self.padding);
+            }
+        }
+        scroll
+    }
+
+    /// Hide the cursor from the cells of a scrolling grid while it is outside of the grid, so cells
+    /// scrolled out of view cannot be interacted with.
+    fn clip(&self, cursor: mouse::Cursor, layout: Layout<'_>) -> mouse::Cursor {
+        if self.scrolls() && !cursor.is_over(layout.bounds()) {
+            mouse::Cursor::Unavailable
+        } else {
+            cursor
+        }
+    }
+
+    /// Hide the cursor from the cell in the given slot of a scrolling grid while it is outside of
+    /// the area the cell is visible in, so cells scrolled under the header or the frozen columns
+    /// cannot be interacted with.
+    fn clip_slot(
+        &self,
+        state: &State,
+        cursor: mouse::Cursor,
+        layout: Layout<'_>,
+        slot: &Slot<'_, M, T, R>,
+    ) -> mouse::Cursor {
+        let cursor = self.clip(cursor, layout);
+        if self.scrolls() && !cursor.is_over(self.slot_area(state, layout.bounds(), slot)) {
+            mouse::Cursor::Unavailable
+        } else {
+            cursor
+        }
+    }
+
+    /// Find the column with the gutter on its right under the cursor, if the columns can be resized.
+    fn resize_handle_at(
+        &self,
+        state: &State,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+    ) -> Option<usize> {
+        let bounds = layout.bounds();
+        let position = cursor.position_over(bounds)?;
+        self.on_column_resize.as_ref()?;
+        let frozen = self.area(state, bounds, Band::Body, true);
+        let tracks = self.column_tracks();
+        state
+            .columns
+            .iter()
+            .enumerate()
+            .position(|(column, track)| {
+                let x = bounds.x + self.column_gutter.mul_add(0.5, track.end());
+                tracks.contains(&column)
+                    && (position.x - x).abs() <= RESIZE_HANDLE_WIDTH / 2.0
+                    && (!self.scrolls()
+                        || column < self.frozen_columns
+                        || x >= frozen.x + frozen.width)
+            })
+    }
+
+    /// Find the tracks that the rows or columns of the kind of the given line can be dropped
+    /// between, leaving out the header.
+    fn droppable<'s>(&self, state: &'s State, line: Line) -> &'s [Track] {
+        match line {
+            Line::Row(_) => {
+                let end = self.body_end().min(state.rows.len());
+                &state.rows[self.header_rows.min(end)..end]
+            }
+            Line::Column(_) => state.columns.get(self.column_tracks()).unwrap_or_default(),
+        }
+    }
+
+    /// Publish the message of the cell under the cursor, returning whether it had one.
+    fn press_cell(
+        &self,
+        state: &State,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        shell: &mut Shell<'_, M>,
+    ) -> bool {
+        self.slot_at(state, layout, cursor)
+            .and_then(|slot| slot.cell.on_press.as_ref())
+            .map(|on_press| shell.publish(on_press()))
+            .is_some()
+    }
+
+    /// Handle the left mouse button being pressed over the grid, pressing and selecting cells and
+    /// starting to drag rows and columns.
+    fn press(
+        &self,
+        state: &mut State,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        shell: &mut Shell<'_, M>,
+    ) -> event::Status {
+        state.focused = self.is_navigable() && cursor.is_over(layout.bounds());
+        if let Some(column) = self.resize_handle_at(state, layout, cursor)
+            && let Some(position) = cursor.position()
+        {
+            let width = state.columns[column].size;
+            state.resize = Some(Resize {
+                column,
+                origin: position.x,
+                initial: width,
+                width,
+            });
+            return event::Status::Captured;
+        }
+        let cell_pressed = self.press_cell(state, layout, cursor, shell);
+        match self.part_at(state, layout, cursor) {
+            Some(Part::Body(row, column)) => {
+                state.focused_cell = Some((row, column));
+                if let Some(on_press) = &self.on_press {
+                    shell.publish(on_press(row, column));
+                }
+                self.double_press(state, row, column, shell);
+                self.select_cell(state.modifiers, row, column, shell);
+                if let Some(on_paint) = &self.on_paint
+                    && let Some(position) = cursor.position()
+                {
+                    shell.publish(on_paint(row, column));
+                    state.paint = Some(Paint {
+                        position,
+                        cell: (row, column),
+                    });
+                } else if self.on_row_reorder.is_some()
+                    && let Some(origin) = cursor.position()
+                {
+                    state.drag = Some(Drag {
+                        line: Line::Row(row),
+                        origin,
+                        moved: false,
+                    });
+                } else if self.on_range_select.is_some() {
+                    state.range = Some(RangeSelection {
+                        start: (row, column),
+                        end: (row, column),
+                    });
+                }
+                if self.on_press.is_some()
+                    || self.on_double_press.is_some()
+                    || self.is_navigable()
+                    || self.on_paint.is_some()
+                    || self.on_row_reorder.is_some()
+                    || self.on_range_select.is_some()
+                {
+                    return event::Status::Captured;
+                }
+            }
+            Some(Part::Header(column)) => {
+                if self.on_column_reorder.is_some()
+                    && let Some(origin) = cursor.position()
+                {
+                    state.drag = Some(Drag {
+                        line: Line::Column(column),
+                        origin,
+                        moved: false,
+                    });
+                    return event::Status::Captured;
+                }
+                if self.sort_by(column, shell) {
+                    return event::Status::Captured;
+                }
+                if let Some(on_column_select) = &self.on_column_select {
+                    shell.publish(on_column_select(column));
+                    return event::Status::Captured;
+                }
+            }
+            Some(Part::RowNumber(row)) => {
+                if let Some(on_row_select) = &self.on_row_select {
+                    shell.publish(on_row_select(row));
+                    return event::Status::Captured;
+                }
+            }
+            Some(Part::ColumnLetter(column)) => {
+                if let Some(on_column_select) = &self.on_column_select {
+                    shell.publish(on_column_select(column));
+                    return event::Status::Captured;
+                }
+            }
+            Some(Part::Footer(_) | Part::Pager | Part::Corner) | None => {}
+        }
+        if let Some(on_row_press) = &self.on_row_press
+            && let Some(row) = self.row_at(state, layout, cursor)
+        {
+            shell.publish(on_row_press(row));
+            return event::Status::Captured;
+        }
+        if cell_pressed {
+            event::Status::Captured
+        } else {
+            event::Status::Ignored
+        }
+    }
+
+    /// Find the row of the body under the cursor, counting the gutters around each row as part of
+    /// the row.
+    fn row_at(&self, state: &State, layout: Layout<'_>, cursor: mouse::Cursor) -> Option<usize> {
+        let bounds = layout.bounds();
+        let y = self.clip(cursor, layout).position_over(bounds)?.y - bounds.y;
+        let half_gutter = self.row_gutter / 2.0;
+        if let Some(header) = self
+            .header_rows
+            .checked_sub(1)
+            .and_then(|row| state.rows.get(row))
+            && y < header.end() + half_gutter
+        {
+            return None;
+        }
+        if let Some(footer) = state.rows.get(self.band_rows(Band::Footer).start)
+            && self.footer_rows > 0
+            && y >= footer.offset - half_gutter
+        {
+            return None;
+        }
+        let end = self.body_end().min(state.rows.len());
+        state
+            .rows
+            .get(self.header_rows.min(end)..end)?
+            .iter()
+            .position(|track| y >= track.offset - half_gutter && y < track.end() + half_gutter)
+            .and_then(|position| self.body_rows.get(position).copied())
+    }
+
+    /// Produce the message for the cell at the given row and column being pressed twice if it was
+    /// the cell pressed last and was pressed recently enough, or remember it was pressed if not.
+    fn double_press(&self, state: &mut State, row: usize, column: usize, shell: &mut Shell<'_, M>) {
+        let Some(on_double_press) = &self.on_double_press else {
+            return;
+        };
+        let now = Instant::now();
+        match state.last_press.take() {
+            Some((pressed, cell))
+                if cell == (row, column)
+                    && now.duration_since(pressed) <= self.double_press_interval =>
+            {
+                shell.publish(on_double_press(row, column));
+            }
+            _ => state.last_press = Some((now, (row, column))),
+        }
+    }
+
+    /// Paint every cell the cursor passes over moving in a straight line from where it was last to
+    /// the given position, sampling the line at least twice across the smallest track so that no
+    /// cell is skipped over when the cursor moves quickly.
+    fn paint(
+        &self,
+        state: &mut State,
+        layout: Layout<'_>,
+        position: Point,
+        shell: &mut Shell<'_, M>,
+    ) {
+        let (Some(on_paint), Some(mut paint)) = (&self.on_paint, state.paint) else {
+            return;
+        };
+        let step = state
+            .columns
+            .iter()
+            .chain(&state.rows)
+            .map(|track| track.size / 2.0)
+            .fold(f32::INFINITY, f32::min)
+            .max(1.0);
+        let delta = position - paint.position;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let steps = (delta.x.hypot(delta.y) / step).ceil().max(1.0) as u32;
+        for sample in 1..=steps {
+            #[allow(clippy::cast_precision_loss)]
+            let sampled = paint.position + delta * (sample as f32 / steps as f32);
+            let cursor = self.clip(mouse::Cursor::Available(sampled), layout);
+            if let Some(Part::Body(row, column)) = self.part_at(state, layout, cursor)
+                && paint.cell != (row, column)
+            {
+                paint.cell = (row, column);
+                shell.publish(on_paint(row, column));
+            }
+        }
+        paint.position = position;
+        state.paint = Some(paint);
+    }
+
+    /// Remember the cell under the cursor once the cursor moves or leaves the window, producing
+    /// the message for the hovered cell changing if it is not the cell that was under the cursor
+    /// before.
+    fn hover(
+        &self,
+        state: &mut State,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        shell: &mut Shell<'_, M>,
+    ) {
+        let Some(on_hover) = &self.on_hover else {
+            return;
+        };
+        let cursor = match event {
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => cursor,
+            Event::Mouse(mouse::Event::CursorLeft) => mouse::Cursor::Unavailable,
+            _ => return,
+        };
+        let hovered = match self.part_at(state, layout, cursor) {
+            Some(Part::Body(row, column)) => Some((row, column)),
+            _ => None,
+        };
+        if hovered != state.hovered {
+            state.hovered = hovered;
+            shell.publish(on_hover(hovered));
+        }
+    }
+
+    /// Handle the left mouse button being released, finishing resizing a column, dragging a row or
+    /// column, selecting a block of cells, or painting cells.
+    fn release(
+        &self,
+        state: &mut State,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        shell: &mut Shell<'_, M>,
+    ) -> event::Status {
+        if let Some(resize) = state.resize.take() {
+            if let Some(on_column_resize) = &self.on_column_resize {
+                shell.publish(on_column_resize(
+                    self.column_position(resize.column - self.column_tracks().start),
+                    Pixels(resize.width),
+                ));
+            }
+            shell.invalidate_layout();
+            return event::Status::Captured;
+        }
+        if let Some(drag) = state.drag.take() {
+            self.drop_dragged(state, drag, layout, cursor, shell);
+            return event::Status::Captured;
+        }
+        if state.paint.take().is_some() {
+            return event::Status::Captured;
+        }
+        if let Some(RangeSelection { start, end }) = state.range.take() {
+            if let Some(on_range_select) = &self.on_range_select {
+                shell.publish(on_range_select(
+                    start.0.min(end.0)..=start.0.max(end.0),
+                    start.1.min(end.1)..=start.1.max(end.1),
+                ));
+            }
+            return event::Status::Captured;
+        }
+        event::Status::Ignored
+    }
+
+    /// Sort the rows by the column at the given index if they can be sorted by it, reversing the
+    /// order if they are already sorted by it. Returns whether the rows were sorted.
+    fn sort_by(&self, column: usize, shell: &mut Shell<'_, M>) -> bool {
+        let Some(on_sort) = &self.on_sort else {
+            return false;
+        };
+        if !self.sortable.get(column).copied().unwrap_or(false) {
+            return false;
+        }
+        let order = match self.sort {
+            Some((sorted, order)) if sorted == column => order.reversed(),
+            _ => SortOrder::Ascending,
+        };
+        shell.publish(on_sort(column, order));
+        true
+    }
+
+    /// Drop the given row or column being dragged where the cursor is if it has been moved, or
+    /// sort the rows by the column being dragged if it has not.
+    fn drop_dragged(
+        &self,
+        state: &State,
+        drag: Drag,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        shell: &mut Shell<'_, M>,
+    ) {
+        if !drag.moved {
+            if let Line::Column(column) = drag.line {
+                self.sort_by(column, shell);
+            }
+            return;
+        }
+        let on_reorder = match drag.line {
+            Line::Row(_) => &self.on_row_reorder,
+            Line::Column(_) => &self.on_column_reorder,
+        };
+        let from = match drag.line {
+            Line::Row(row) => self.body_rows.iter().position(|&body_row| body_row == row),
+            Line::Column(column) => Some(self.column_position(column)),
+        };
+        if let Some(on_reorder) = on_reorder
+            && let Some(from) = from
+            && let Some(position) = cursor.position()
+        {
+            let gap = gap_at(
+                self.droppable(state, drag.line),
+                drag.line.along(position) - drag.line.along(layout.bounds().position()),
+            );
+            let to = if gap > from { gap - 1 } else { gap };
+            let index = |position: usize| match drag.line {
+                Line::Row(_) => self.body_rows[position],
+                Line::Column(_) => self.column_position(position),
+            };
+            if to != from {
+                shell.publish(on_reorder(index(from), index(to)));
+            }
+        }
+    }
+
+    /// Find which slot covers each position in the grid, if any.
+    fn coverage(&self, state: &State) -> Vec<Vec<Option<usize>>> {
+        let mut coverage = vec![vec![None; state.columns.len()]; state.rows.len()];
+        for (index, slot) in self.slots.iter().enumerate() {
+            for covered in &mut coverage[slot.row..slot.row + slot.cell.row_span] {
+                covered[slot.column..slot.column + slot.cell.column_span].fill(Some(index));
+            }
+        }
+        coverage
+    }
+
+    /// Draw lines in the gutters before the given rows and columns of the grid, leaving out the
+    /// parts of the lines that would cut through cells spanning multiple tracks.
+    fn draw_lines(
+        &self,
+        state: &State,
+        renderer: &mut R,
+        bounds: Rectangle,
+        rows: Range<usize>,
+        columns: Range<usize>,
+    ) {
+        let Some((width, color)) = self.lines else {
+            return;
+        };
+        let coverage = self.coverage(state);
+        let mut line = |x: f32, y: f32, line_width: f32, line_height: f32| {
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: Rectangle::new(
+                        Point::new(bounds.x + x, bounds.y + y),
+                        Size::new(line_width, line_height),
+                    ),
+                    ..renderer::Quad::default()
+                },
+                color,
+            );
+        };
+        for (column, pair) in state.columns.windows(2).enumerate() {
+            if !columns.contains(&(column + 1)) {
+                continue;
+            }
+            let x = (pair[0].end() + pair[1].offset - width) / 2.0;
+            for (row, covered) in coverage.iter().enumerate() {
+                if !rows.contains(&row)
+                    || covered[column].is_some() && covered[column] == covered[column + 1]
+                {
+                    continue;
+                }
+                let (top, bottom) = line_extent(&state.rows, row, state.gutters.y);
+                line(x, top, width, bottom - top);
+            }
+        }
+        for (row, pair) in state.rows.windows(2).enumerate() {
+            if !rows.contains(&(row + 1)) {
+                continue;
+            }
+            let y = (pair[0].end() + pair[1].offset - width) / 2.0;
+            for (column, (above, below)) in coverage[row].iter().zip(&coverage[row + 1]).enumerate()
+            {
+                if !columns.contains(&column) || above.is_some() && above == below {
+                    continue;
+                }
+                let (left, right) = line_extent(&state.columns, column, state.gutters.x);
+                line(left, y, right - left, width);
+            }
+        }
+    }
+
+    /// Get the elements of the grid, which are the elements of its cells followed by its
+    /// separators, in the order of the children of its tree and layout.
+    fn elements(&self) -> Vec<&Element<'a, M, T, R>> {
+        self.slots
+            .iter()
+            .map(|slot| &slot.cell.element)
+            .chain(
+                self.row_separators
+                    .iter()
+                    .chain(&self.column_separators)
+                    .map(|(_, element)| element),
+            )
+            .collect()
+    }
+
+    /// Find what each child of the grid shows, in the order of the children of its tree.
+    fn child_keys(&self) -> Vec<Child> {
+        self.slots
+            .iter()
+            .map(|slot| match slot.part {
+                Part::Body(row, column) => Child::Cell(self.row_id(row), column),
+                part => Child::Part(part),
+            })
+            .chain(
+                self.row_separators
+                    .iter()
+                    .map(|&(row, _)| Child::RowSeparator(row)),
+            )
+            .chain(
+                self.column_separators
+                    .iter()
+                    .map(|&(column, _)| Child::ColumnSeparator(column)),
+            )
+            .collect()
+    }
+
+    /// Lay out the separators in the gutters between the given tracks, centered in the gutters and
+    /// stretching across the tracks the other way, given the trees of the separators.
+    fn layout_separators(
+        &self,
+        trees: &mut [Tree],
+        renderer: &R,
+        columns: &[Track],
+        rows: &[Track],
+    ) -> Vec<layout::Node> {
+        let extent = |tracks: &[Track]| {
+            tracks.first().map_or((0.0, 0.0), |first| {
+                (first.offset, tracks.last().map_or(0.0, |last| last.end()))
+            })
+        };
+        let (left, right) = extent(columns);
+        let (top, bottom) = extent(rows);
+        let gaps = self
+            .row_separators
+            .iter()
+            .map(|(row, element)| {
+                let position = Point::new(left, rows[row - 1].end());
+                let size = Size::new(right - left, rows[*row].offset - position.y);
+                (element, position, size)
+            })
+            .chain(self.column_separators.iter().map(|(column, element)| {
+                let position = Point::new(columns[column - 1].end(), top);
+                let size = Size::new(columns[*column].offset - position.x, bottom - top);
+                (element, position, size)
+            }));
+        gaps.zip(trees)
+            .map(|((element, position, size), tree)| {
+                let content = element
+                    .as_widget()
+                    .layout(tree, renderer, &layout::Limits::new(Size::ZERO, size))
+                    .align(Alignment::Center, Alignment::Center, size);
+                layout::Node::with_children(size, vec![content]).move_to(position)
+            })
+            .collect()
+    }
+
+    /// Draw the separators in the gutters between rows and columns, beneath the cells.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_separators(
+        &self,
+        tree: &Tree,
+        renderer: &mut R,
+        theme: &T,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        for (((_, element), tree), layout) in self
+            .row_separators
+            .iter()
+            .chain(&self.column_separators)
+            .zip(tree.children.iter().skip(self.slots.len()))
+            .zip(layout.children().skip(self.slots.len()))
+        {
+            if layout.bounds().intersection(viewport).is_some() {
+                element.as_widget().draw(
+                    tree,
+                    renderer,
+                    theme,
+                    style,
+                    content(layout),
+                    cursor,
+                    viewport,
+                );
+            }
+        }
+    }
+
+    /// Draw what is drawn over the cells of the grid: where a dragged row or column would be
+    /// dropped, the block of cells being selected and the focus ring.
+    fn draw_indicators(
+        &self,
+        state: &State,
+        renderer: &mut R,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        color: Color,
+    ) {
+        self.draw_drop_indicator(state, renderer, layout.bounds(), cursor, color);
+        self.draw_range_selection(state, renderer, layout, color);
+        self.draw_focus_ring(state, renderer, layout, color);
+    }
+
+    /// Draw a translucent rectangle over the block of cells being selected by dragging, if any.
+    fn draw_range_selection(
+        &self,
+        state: &State,
+        renderer: &mut R,
+        layout: Layout<'_>,
+        color: Color,
+    ) {
+        let Some(RangeSelection { start, end }) =
+            state.range.filter(|range| range.start != range.end)
+        else {
+            return;
+        };
+        let bounds_of = |cell: (usize, usize)| {
+            self.slots
+                .iter()
+                .zip(layout.children())
+                .find(|(slot, _)| slot.part == Part::Body(cell.0, cell.1))
+                .map(|(_, layout)| layout.bounds())
+        };
+        if let (Some(start), Some(end)) = (bounds_of(start), bounds_of(end)) {
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: start.union(&end),
+                    border: Border {
+                        color,
+                        width: 1.0,
+                        ..Border::default()
+                    },
+                    ..renderer::Quad::default()
+                },
+                color.scale_alpha(RANGE_SELECTION_ALPHA),
+            );
+        }
+    }
+
+    /// Draw the focus ring around the focused cell while the grid is focused, in the given color
+    /// unless the grid has its own focus ring.
+    fn draw_focus_ring(&self, state: &State, renderer: &mut R, layout: Layout<'_>, color: Color) {
+        if !state.focused {
+            return;
+        }
+        let Some((row, column)) = self.focused_cell(state) else {
+            return;
+        };
+        let Some(bounds) = self
+            .slots
+            .iter()
+            .zip(layout.children())
+            .find(|(slot, _)| slot.part == Part::Body(row, column))
+            .map(|(_, layout)| layout.bounds())
+        else {
+            return;
+        };
+        let border = self.focus_ring.unwrap_or_else(|| Border {
+            color,
+            width: DROP_INDICATOR_WIDTH,
+            ..Border::default()
+        });
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds,
+                border,
+                ..renderer::Quad::default()
+            },
+            Color::TRANSPARENT,
+        );
+    }
+
+    /// Draw a line in the gap a dragged row or column would be dropped into.
+    fn draw_drop_indicator(
+        &self,
+        state: &State,
+        renderer: &mut R,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+        color: Color,
+    ) {
+        let Some(Drag {
+            line, moved: true, ..
+        }) = state.drag
+        else {
+            return;
+        };
+        let Some(position) = cursor.position() else {
+            return;
+        };
+        let tracks = self.droppable(state, line);
+        let offset = gap_offset(
+            tracks,
+            gap_at(tracks, line.along(position) - line.along(bounds.position())),
+        ) - DROP_INDICATOR_WIDTH / 2.0;
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: match line {
+                    Line::Row(_) => Rectangle {
+                        y: bounds.y + offset,
+                        height: DROP_INDICATOR_WIDTH,
+                        ..bounds
+                    },
+                    Line::Column(_) => Rectangle {
+                        x: bounds.x + offset,
+                        width: DROP_INDICATOR_WIDTH,
+                        ..bounds
+                    },
+                },
+                ..renderer::Quad::default()
+            },
+            color,
+        );
+    }
+}
+
+impl<M, T, R: advanced::Renderer> Widget<M, T, R> for Instance<'_, M, T, R> {
+    fn size(&self) -> Size<Length> {
+        let width = if self.scrollable || self.justify_content != Distribution::Start {
+            Length::Fill
+        } else {
+            fill_or_shrink(&self.column_widths)
+        };
+        let height = match self.viewport_height {
+            Some(viewport_height) => Length::Fixed(viewport_height),
+            None if self.scrollable || self.align_content != Distribution::Start => Length::Fill,
+            None => fill_or_shrink(&self.row_heights),
+        };
+        Size::new(width, height)
+    }
+
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State {
+            children: self.child_keys(),
+            ..State::default()
+        })
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.elements().into_iter().map(Tree::new).collect()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        let state = tree.state.downcast_mut::<State>();
+        let keys = self.child_keys();
+        let mut previous: HashMap<Child, Vec<Tree>> = HashMap::new();
+        for (key, child) in state
+            .children
+            .iter()
+            .zip(std::mem::take(&mut tree.children))
+            .rev()
+        {
+            previous.entry(*key).or_default().push(child);
+        }
+        tree.children = keys
+            .iter()
+            .zip(self.elements())
+            .map(|(key, element)| {
+                let mut child = previous
+                    .get_mut(key)
+                    .and_then(Vec::pop)
+                    .unwrap_or_else(|| Tree::new(element));
+                child.diff(element);
+                child
+            })
+            .collect();
+        state.children = keys;
+    }
+
+    fn layout(&self, tree: &mut Tree, renderer: &R, limits: &layout::Limits) -> layout::Node {
+        let max = limits.max();
+        tree.state.downcast_mut::<State>().available_width = max.width;
+        let available = Size::new(
+            2.0f32.mul_add(
+                -self.padding,
+                max.width - gutters(self.column_widths.len(), self.column_gutter),
+            ),
+            2.0f32.mul_add(
+                -self.padding,
+                max.height - gutters(self.row_heights.len(), self.row_gutter),
+            ),
+        );
+
+        let column_widths = self.measure_columns(tree, renderer, available);
+        let row_heights = self.measure_rows(tree, renderer, available, &column_widths);
+
+        let (mut column_offsets, width) = offsets(&column_widths, self.column_gutter, self.padding);
+        let (mut row_offsets, height) = offsets(&row_heights, self.row_gutter, self.padding);
+        let size = self.size();
+        let size = limits.resolve(
+            size.width,
+            size.height,
+            Size::new(
+                self.padding.mul_add(2.0, width),
+                self.padding.mul_add(2.0, height),
+            ),
+        );
+        let justify_content = if self.right_to_left {
+            self.justify_content.reversed()
+        } else {
+            self.justify_content
+        };
+        let column_gutter = self.column_gutter
+            + distribute(
+                &mut column_offsets,
+                size.width - self.padding.mul_add(2.0, width),
+                justify_content,
+            );
+        let row_gutter = self.row_gutter
+            + distribute(
+                &mut row_offsets,
+                size.height - self.padding.mul_add(2.0, height),
+                self.align_content,
+            );
+        let state = tree.state.downcast_mut::<State>();
+        state.gutters = Vector::new(column_gutter, row_gutter);
+        state.columns = tracks(&column_offsets, &column_widths);
+        state.rows = tracks(&row_offsets, &row_heights);
+        self.transition_rows(state);
+        if self.scrolls() {
+            let content = Size::new(
+                self.padding.mul_add(2.0, width),
+                self.padding.mul_add(2.0, height),
+            );
+            self.scroll(state, content, size);
+        }
+        let split = self.slots.len().min(tree.children.len());
+        let (cells, separators) = tree.children.split_at_mut(split);
+        let children = self
+            .slots
+            .iter()
+            .zip(cells)
+            .map(|(slot, tree)| {
+                let size = Size::new(
+                    span_length(
+                        &column_widths,
+                        slot.column,
+                        slot.cell.column_span,
+                        column_gutter,
+                    ),
+                    span_length(&row_heights, slot.row, slot.cell.row_span, row_gutter),
+                );
+                let content = slot
+                    .cell
+                    .element
+                    .as_widget()
+                    .layout(tree, renderer, &layout::Limits::new(Size::ZERO, size))
+                    .align(
+                        Alignment::from(slot.cell.align_x),
+                        Alignment::from(slot.cell.align_y),
+                        size,
+                    );
+                layout::Node::with_children(size, vec![content]).move_to(Point::new(
+                    state.columns[slot.column].offset,
+                    state.rows[slot.row].offset,
+                ))
+            })
+            .chain(self.layout_separators(separators, renderer, &state.columns, &state.rows))
+            .collect();
+        layout::Node::with_children(size, children)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut R,
+        theme: &T,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_ref::<State>();
+        let bounds = layout.bounds();
+        let viewport = if self.scrolls() {
+            bounds.intersection(viewport)
+        } else {
+            Some(*viewport)
+        };
+        let Some(viewport) = viewport else {
+            return;
+        };
+        self.draw_frame(renderer, theme, bounds);
+        let draw_part = |renderer: &mut R, band: Band, frozen: bool, viewport: Rectangle| {
+            for ((slot, tree), layout) in
+                self.slots.iter().zip(&tree.children).zip(layout.children())
+            {
+                let bounds = layout.bounds();
+                if self.band(slot) != band
+                    || (slot.column < self.frozen_columns) != frozen
+                    || bounds.intersection(&viewport).is_none()
+                {
+                    continue;
+                }
+                let cursor = self.clip_slot(state, cursor, layout, slot);
+                let transition = self.transition(state, slot);
+                let cell_style = self.cell_style(state, theme, slot, bounds, cursor, transition);
+                let background = if self.collapse_borders {
+                    collapsed(bounds, slot, cell_style.border.width)
+                } else {
+                    bounds
+                };
+                let text_color = cell_style.text_color.unwrap_or(style.text_color);
+                let draw = |renderer: &mut R, viewport: &Rectangle| {
+                    slot.cell.element.as_widget().draw(
+                        tree,
+                        renderer,
+                        theme,
+                        &renderer::Style { text_color },
+                        content(layout),
+                        cursor,
+                        viewport,
+                    );
+                };
+                let draw_cell = |renderer: &mut R| {
+                    container::draw_background(renderer, &cell_style, background);
+                    if slot.cell.clip {
+                        if let Some(clipped) = bounds.intersection(&viewport) {
+                            renderer.with_layer(clipped, |renderer| draw(renderer, &clipped));
+                        }
+                    } else {
+                        draw(renderer, &viewport);
+                    }
+                };
+                match transition {
+                    Some((_, offset)) => {
+                        renderer.with_translation(Vector::new(0.0, offset), draw_cell);
+                    }
+                    None => draw_cell(renderer),
+                }
+                if let Some((column, order)) = self.sort
+                    && slot.part == Part::Header(column)
+                {
+                    draw_sort_indicator(renderer, bounds, order, text_color);
+                }
+            }
+            let rows = self.band_rows(band);
+            let columns = if frozen {
+                0..self.frozen_columns
+            } else {
+                self.frozen_columns..state.columns.len()
+            };
+            self.draw_lines(state, renderer, bounds, rows, columns);
+        };
+        let parts = [Band::Body, Band::Header, Band::Footer]
+            .into_iter()
+            .flat_map(|band| [(band, false), (band, true)]);
+        if self.scrolls() {
+            renderer.with_layer(bounds, |renderer| {
+                for (band, frozen) in parts {
+                    let area = self.area(state, bounds, band, frozen);
+                    if let Some(viewport) = area.intersection(&viewport) {
+                        renderer.with_layer(area, |renderer| {
+                            self.draw_separators(
+                                tree, renderer, theme, style, layout, cursor, &viewport,
+                            );
+                            draw_part(renderer, band, frozen, viewport);
+                        });
+                    }
+                }
+                self.draw_indicators(state, renderer, layout, cursor, style.text_color);
+            });
+        } else {
+            self.draw_separators(tree, renderer, theme, style, layout, cursor, &viewport);
+            for (band, frozen) in parts {
+                draw_part(renderer, band, frozen, viewport);
+            }
+            self.draw_indicators(state, renderer, layout, cursor, style.text_color);
+        }
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &R,
+        operation: &mut dyn Operation,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+        if self.is_navigable() {
+            operation.focusable(state, self.id.as_ref());
+        }
+        operation.custom(state, self.id.as_ref());
+        operation.container(self.id.as_ref(), layout.bounds(), &mut |operation| {
+            for ((slot, tree), layout) in self
+                .slots
+                .iter()
+                .zip(&mut tree.children)
+                .zip(layout.children())
+            {
+                slot.cell
+                    .element
+                    .as_widget()
+                    .operate(tree, content(layout), renderer, operation);
+            }
+        });
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &R,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, M>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        self.hover(tree.state.downcast_mut(), &event, layout, cursor, shell);
+        if tree.state.downcast_ref::<State>().scroll_target.is_some() {
+            shell.invalidate_layout();
+        }
+        self.animate(tree.state.downcast_mut(), &event, shell);
+        self.request_rows(tree.state.downcast_mut(), layout, shell);
+        let state = tree.state.downcast_ref::<State>();
+        let cursors: Vec<_> = self
+            .slots
+            .iter()
+            .map(|slot| self.clip_slot(state, cursor, layout, slot))
+            .collect();
+        let status = self
+            .slots
+            .iter_mut()
+            .zip(&mut tree.children)
+            .zip(layout.children())
+            .zip(cursors)
+            .map(|(((slot, tree), child), cursor)| {
+                slot.cell.element.as_widget_mut().on_event(
+                    tree,
+                    event.clone(),
+                    content(child),
+                    cursor,
+                    renderer,
+                    clipboard,
+                    shell,
+                    viewport,
+                )
+            })
+            .fold(event::Status::Ignored, event::Status::merge);
+        if status == event::Status::Captured {
+            return status;
+        }
+
+        let state = tree.state.downcast_mut::<State>();
+        match event {
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                state.modifiers = modifiers;
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. })
+                if state.focused =>
+            {
+                return self.key_pressed(state, &key, modifiers, clipboard, shell, viewport);
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                return self.press(state, layout, cursor, shell);
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
+                if let Some(on_right_press) = &self.on_right_press
+                    && let Some(Part::Body(row, column)) = self.part_at(state, layout, cursor)
+                {
+                    shell.publish(on_right_press(row, column));
+                    return event::Status::Captured;
+                }
+            }
+            Event::Mouse(mouse::Event::WheelScrolled { delta })
+                if self.scrolls() && cursor.is_over(layout.bounds()) =>
+            {
+                let delta = match delta {
+                    mouse::ScrollDelta::Lines { x, y } => Vector::new(x, y) * LINE_HEIGHT,
+                    mouse::ScrollDelta::Pixels { x, y } => Vector::new(x, y),
+                };
+                state.scroll_animation = None;
+                state.scroll = Vector::new(
+                    (state.scroll.x - delta.x).clamp(0.0, state.max_scroll.x),
+                    (state.scroll.y - delta.y).clamp(0.0, state.max_scroll.y),
+                );
+                shell.invalidate_layout();
+                return event::Status::Captured;
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                if let Some(resize) = &mut state.resize {
+                    resize.width = (resize.initial + position.x - resize.origin).max(0.0);
+                    shell.invalidate_layout();
+                    return event::Status::Captured;
+                }
+                if let Some(drag) = &mut state.drag {
+                    drag.moved |= (drag.line.along(position) - drag.line.along(drag.origin)).abs()
+                        > DRAG_THRESHOLD;
+                    return event::Status::Captured;
+                }
+                if state.paint.is_some() {
+                    self.paint(state, layout, position, shell);
+                    return event::Status::Captured;
+                }
+                if state.range.is_some() {
+                    if let Some(Part::Body(row, column)) = self.part_at(state, layout, cursor)
+                        && let Some(range) = &mut state.range
+                    {
+                        range.end = (row, column);
+                    }
+                    return event::Status::Captured;
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                return self.release(state, layout, cursor, shell);
+            }
+            _ => {}
+        }
+        event::Status::Ignored
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &R,
+    ) -> mouse::Interaction {
+        let state = tree.state.downcast_ref::<State>();
+        let cursor = self.clip(cursor, layout);
+        if state.resize.is_some() || self.resize_handle_at(state, layout, cursor).is_some() {
+            return mouse::Interaction::ResizingHorizontally;
+        }
+        if let Some(Drag { moved: true, .. }) = state.drag {
+            return mouse::Interaction::Grabbing;
+        }
+        self.slots
+            .iter()
+            .zip(&tree.children)
+            .zip(layout.children())
+            .map(|((slot, tree), layout)| {
+                slot.cell.element.as_widget().mouse_interaction(
+                    tree,
+                    content(layout),
+                    self.clip_slot(state, cursor, layout, slot),
+                    viewport,
+                    renderer,
+                )
+            })
+            .max()
+            .unwrap_or_default()
+            .max(match self.part_at(state, layout, cursor) {
+                Some(Part::Body(..)) if self.on_paint.is_some() => mouse::Interaction::Crosshair,
+                Some(Part::Body(..)) if self.on_row_reorder.is_some() => mouse::Interaction::Grab,
+                Some(Part::Body(..))
+                    if self.on_press.is_some()
+                        || self.on_double_press.is_some()
+                        || self.is_navigable() =>
+                {
+                    mouse::Interaction::Pointer
+                }
+                Some(Part::Header(column))
+                    if self.on_sort.is_some()
+                        && self.sortable.get(column).copied().unwrap_or(false) =>
+                {
+                    mouse::Interaction::Pointer
+                }
+                Some(Part::Header(..)) if self.on_column_reorder.is_some() => {
+                    mouse::Interaction::Grab
+                }
+                Some(Part::Header(..) | Part::ColumnLetter(..))
+                    if self.on_column_select.is_some() =>
+                {
+                    mouse::Interaction::Pointer
+                }
+                Some(Part::RowNumber(..)) if self.on_row_select.is_some() => {
+                    mouse::Interaction::Pointer
+                }
+                _ if self
+                    .slot_at(state, layout, cursor)
+                    .is_some_and(|slot| slot.cell.on_press.is_some()) =>
+                {
+                    mouse::Interaction::Pointer
+                }
+                _ if self.on_row_press.is_some()
+                    && self.row_at(state, layout, cursor).is_some() =>
+                {
+                    mouse::Interaction::Pointer
+                }
+                _ => mouse::Interaction::None,
+            })
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &R,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, M, T, R>> {
+        let visible = self.visible(tree.state.downcast_ref(), layout);
+        let elements = self
+            .slots
+            .iter_mut()
+            .map(|slot| &mut slot.cell.element)
+            .chain(
+                self.row_separators
+                    .iter_mut()
+                    .chain(&mut self.column_separators)
+                    .map(|(_, element)| element),
+            );
+        let children = elements
+            .zip(&mut tree.children)
+            .zip(layout.children())
+            .zip(visible)
+            .filter(|(_, visible)| *visible)
+            .filter_map(|(((element, tree), layout), _)| {
+                element
+                    .as_widget_mut()
+                    .overlay(tree, content(layout), renderer, translation)
+            })
+            .collect::<Vec<_>>();
+        (!children.is_empty()).then(|| overlay::Group::with_children(children).overlay())
+    }
+}
+
+/// An operation scrolling the grid with the given identifier until a cell is in view.
+struct ScrollTo {
+    id: widget::Id,
+    cell: (usize, usize),
+}
+
+impl<O> Operation<O> for ScrollTo {
+    fn container(
+        &mut self,
+        _id: Option<&widget::Id>,
+        _bounds: Rectangle,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<O>),
+    ) {
+        operate_on_children(self);
+    }
+
+    fn custom(&mut self, state: &mut dyn Any, id: Option<&widget::Id>) {
+        if id == Some(&self.id)
+            && let Some(state) = state.downcast_mut::<State>()
+        {
+            state.scroll_target = Some(self.cell);
+        }
+    }
+}
+
+/// Produce a task scrolling the [`Grid`] with the given identifier, set with [`Grid::id`], until
+/// the cell at the given row and column is in view, like jumping to a row of a log.
+///
+/// Only grids that scroll, because they are [scrollable](Grid::scrollable) or
+/// [virtualized](Grid::virtualized), can be scrolled. The grid is scrolled as little as possible,
+/// so a cell that is already in view stays where it is, and the grid is not scrolled to cells in
+/// the header, in frozen columns, or on other pages than the page being shown.
+pub fn scroll_to<O: Send + 'static>(id: widget::Id, row: usize, column: usize) -> Task<O> {
+    widget::operate(ScrollTo {
+        id,
+        cell: (row, column),
+    })
+}
+
+/// Get the layout of the element inside the layout of a slot.
+fn content(layout: Layout<'_>) -> Layout<'_> {
+    layout
+        .children()
+        .next()
+        .expect("every slot should contain exactly one element")
+}
+
+impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a> From<Instance<'a, M, T, R>>
+    for Element<'a, M, T, R>
+{
+    fn from(instance: Instance<'a, M, T, R>) -> Self {
+        Self::new(instance)
+    }
+}
+
+impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a> Grid<'a, M, T, R> {
+    /// Find the window of rows to create the cells of, which is every row on the page being shown
+    /// unless the grid is virtualized. A virtualized grid that has not been laid out yet starts
+    /// with no rows.
+    fn window(&self, state: Option<&State>) -> Range<usize> {
+        let page = self.page_rows();
+        let Some(viewport_height) = self.virtualized else {
+            return page;
+        };
+        let Some(state) = state else {
+            return 0..0;
+        };
+        let header_rows = self.header_rows(self.reflow(Some(state)));
+        let rows = &state.rows[header_rows.min(state.rows.len())..];
+        let start = rows.iter().take_while(|track| track.end() < 0.0).count();
+        let end = rows
+            .iter()
+            .take_while(|track| track.offset < viewport_height.0)
+            .count();
+        page.start + start.saturating_sub(OVERSCAN)..(page.start + end + OVERSCAN).min(page.end)
+    }
+
+    /// Get the instance of the grid, creating it the first time the grid is used as a widget.
+    fn instance(&self) -> Ref<'_, Instance<'a, M, T, R>> {
+        if self.instance.borrow().is_none() {
+            *self.instance.borrow_mut() = Some(self.instantiate(self.window(None), None));
+        }
+        Ref::map(self.instance.borrow(), |instance| {
+            instance
+                .as_ref()
+                .expect("the instance should have just been created")
+        })
+    }
+
+    /// Get a mutable reference to the instance of the grid, creating it the first time the grid is
+    /// used as a widget.
+    fn instance_mut(&mut self) -> &mut Instance<'a, M, T, R> {
+        if self.instance.get_mut().is_none() {
+            *self.instance.get_mut() = Some(self.instantiate(self.window(None), None));
+        }
+        self.instance
+            .get_mut()
+            .as_mut()
+            .expect("the instance should have just been created")
+    }
+
+    /// Find the number of columns to reflow the cells of a responsive grid into, fitting as many
+    /// columns as the width the grid was last given allows. A grid that has not been laid out yet
+    /// is not reflowed.
+    fn reflow(&self, state: Option<&State>) -> Option<usize> {
+        let min_cell_width = self.responsive?.0;
+        let width = state?.available_width;
+        if !width.is_finite() {
+            return None;
+        }
+        let width = self.padding.0.mul_add(-2.0, width) + self.column_gutter.0;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let columns = (width / (min_cell_width + self.column_gutter.0)).floor() as usize;
+        Some(columns.max(1))
+    }
+
+    /// Recreate the instance of a virtualized or responsive grid if the rows in view or the number
+    /// of columns have changed since it was created, returning whether it was recreated.
+    fn refresh(&self, tree: &mut Tree) -> bool {
+        if self.virtualized.is_none() && self.responsive.is_none() {
+            return false;
+        }
+        let state = tree.state.downcast_ref();
+        let window = self.window(Some(state));
+        let reflow = self.reflow(Some(state));
+        if self
+            .instance
+            .borrow()
+            .as_ref()
+            .is_some_and(|instance| instance.window == window && instance.reflow == reflow)
+        {
+            return false;
+        }
+        *self.instance.borrow_mut() = Some(self.instantiate(window, reflow));
+        self.instance().diff(tree);
+        true
+    }
+}
+
+impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a> Widget<M, T, R> for Grid<'a, M, T, R> {
+    fn size(&self) -> Size<Length> {
+        self.instance().size()
+    }
+
+    fn tag(&self) -> tree::Tag {
+        self.instance().tag()
+    }
+
+    fn state(&self) -> tree::State {
+        self.instance().state()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.instance().children()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        if !self.refresh(tree) {
+            self.instance().diff(tree);
+        }
+    }
+
+    fn layout(&self, tree: &mut Tree, renderer: &R, limits: &layout::Limits) -> layout::Node {
+        self.refresh(tree);
+        let node = self.instance().layout(tree, renderer, limits);
+        if self.refresh(tree) {
+            self.instance().layout(tree, renderer, limits)
+        } else {
+            node
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut R,
+        theme: &T,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.instance()
+            .draw(tree, renderer, theme, style, layout, cursor, viewport);
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &R,
+        operation: &mut dyn Operation,
+    ) {
+        self.instance().operate(tree, layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &R,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, M>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        let status = self.instance_mut().on_event(
+            tree, event, layout, cursor, renderer, clipboard, shell, viewport,
+        );
+        if self.instance_mut().copying.take()
+            && let Some(text) = self.selection_text()
+        {
+            clipboard.write(clipboard::Kind::Standard, text);
+        }
+        status
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &R,
+    ) -> mouse::Interaction {
+        self.instance()
+            .mouse_interaction(tree, layout, cursor, viewport, renderer)
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &R,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, M, T, R>> {
+        self.instance_mut()
+            .overlay(tree, layout, renderer, translation)
+    }
+}
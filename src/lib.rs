@@ -1,36 +1,346 @@
 //! # grid
-//! A thin wrapper around `iced`'s `Column` widget to create a grid of cells.
+//! A grid widget for `iced`, laying out cells in rows and columns.
+//!
+//! A [`Grid`] can be turned into an [`Element`] directly, or kept in the state of an application
+//! and borrowed to create a new [`Element`] on each `view` cycle.
 #![warn(missing_docs, clippy::pedantic, clippy::nursery)]
 use iced::{
-    Element, Pixels, Theme, advanced,
+    Alignment, Background, Border, Color, Element, Length, Pixels, Shadow, Theme,
+    advanced::{self, widget},
+    alignment::{Horizontal, Vertical},
+    border::Radius,
     widget::{
-        Column, Container, Row,
+        Row, Space, button,
         container::{self, Style, StyleFn},
+        scrollable::{self, Scrollable},
+        text,
+        text_input::{self, TextInput},
+        tooltip::{self, Tooltip},
     },
 };
+use instance::{Instance, Part, Placement, Separators, Slot};
+use renderers::Registry;
+use std::{
+    any::Any,
+    borrow::Cow,
+    cell::RefCell,
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::{DefaultHasher, Hash, Hasher},
+    ops::{Index, IndexMut, Range, RangeInclusive},
+    rc::Rc,
+    time::Duration,
+};
+
+#[cfg(feature = "calendar")]
+mod calendar;
+pub mod cells;
+pub mod column_types;
+mod format;
+pub mod formatting;
+mod heatmap;
+mod instance;
+pub mod renderers;
+pub mod style;
+
+#[cfg(feature = "calendar")]
+pub use calendar::Calendar;
+pub use format::{CellFormat, Value};
+pub use heatmap::Heatmap;
+pub use instance::scroll_to;
+pub use style::Catalog;
+
+/// Create a [`Grid`] with the given rows of cells, like `iced`'s `column!` and `row!` macros.
+///
+/// Each row is written as a list of anything that can be converted into a [`Factory`].
+///
+/// ```ignore
+/// let grid = grid![
+///     ["a", "b", "c"],
+///     ["d", "e", "f"],
+/// ];
+/// ```
+#[macro_export]
+macro_rules! grid {
+    () => (
+        $crate::Grid::new()
+    );
+    ($([$($cell:expr),* $(,)?]),+ $(,)?) => (
+        $crate::Grid::new()$(.with_row([$($crate::Factory::from($cell)),*]))+
+    );
+}
 
 /// A cell in a grid, storing information about the inner element and the style of the cell.
-pub struct Cell<'a, M, T, R>(Element<'a, M, T, R>, Style);
+pub struct Cell<'a, M, T, R> {
+    element: Element<'a, M, T, R>,
+    style: StyleFn<'a, T>,
+    hovered_style: Option<Style>,
+    row_span: usize,
+    column_span: usize,
+    align_x: Horizontal,
+    align_y: Vertical,
+    text: Option<String>,
+    area: Option<String>,
+    placeholder: bool,
+    clip: bool,
+    on_press: Option<Box<dyn Fn() -> M + 'a>>,
+    cached: Option<CachedElement<'a, M, T, R>>,
+    validator: Option<Validator<'a>>,
+}
+
+/// A function creating the element of a cached cell, given the version the grid gives the cell.
+type CachedElement<'a, M, T, R> = Box<dyn FnOnce(u64) -> Element<'a, M, T, R> + 'a>;
+
+/// A function checking a value typed into a cell being edited, giving the error to show if the
+/// value is invalid.
+type Validator<'a> = Box<dyn Fn(&str) -> Result<(), String> + 'a>;
 
 impl<'a, M, T, R, E: Into<Element<'a, M, T, R>> + 'a> From<E> for Cell<'a, M, T, R> {
     /// Create a new grid cell with an element in it. The style of the grid cell is set to the default style. See
     /// the implementation of [`Default`] for [`Style`] for more information.
+    ///
+    /// The cell spans a single row and a single column, and its element is centered in it.
     fn from(element: E) -> Self {
-        Self(element.into(), Style::default())
+        Self {
+            element: element.into(),
+            style: Box::new(|_| Style::default()),
+            hovered_style: None,
+            row_span: 1,
+            column_span: 1,
+            align_x: Horizontal::Center,
+            align_y: Vertical::Center,
+            text: None,
+            area: None,
+            placeholder: false,
+            clip: false,
+            on_press: None,
+            cached: None,
+            validator: None,
+        }
+    }
+}
+
+impl<M, T, R> fmt::Debug for Cell<'_, M, T, R> {
+    /// Format the cell, leaving out the element inside it and its style.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cell")
+            .field("hovered_style", &self.hovered_style)
+            .field("row_span", &self.row_span)
+            .field("column_span", &self.column_span)
+            .field("align_x", &self.align_x)
+            .field("align_y", &self.align_y)
+            .field("text", &self.text)
+            .field("area", &self.area)
+            .field("placeholder", &self.placeholder)
+            .field("clip", &self.clip)
+            .field("on_press", &self.on_press.is_some())
+            .field("validator", &self.validator.is_some())
+            .finish_non_exhaustive()
     }
 }
 
-impl<M, T, R> Cell<'_, M, T, R> {
+impl<'a, M, T, R> Cell<'a, M, T, R> {
     /// Set the style of the cell.
     #[must_use]
     pub fn style(mut self, style: impl Into<Style>) -> Self {
-        self.1 = style.into();
+        let style = style.into();
+        self.style = Box::new(move |_| style);
+        self
+    }
+
+    /// Set the style of the cell to be created from the current theme, like the style of a
+    /// [`Container`](iced::widget::Container), so that the cell can pick its colors from the
+    /// palette of the theme.
+    #[must_use]
+    pub fn style_fn(mut self, style: impl Fn(&T) -> Style + 'a) -> Self {
+        self.style = Box::new(style);
+        self
+    }
+
+    /// Set the style of the cell while the cursor is over it. The style is layered on top of the
+    /// usual style of the cell, so parts of it that are left unset, like the text color, stay the
+    /// same as usual.
+    #[must_use]
+    pub fn style_hovered(mut self, style: impl Into<Style>) -> Self {
+        self.hovered_style = Some(style.into());
+        self
+    }
+
+    /// Set the number of columns the cell spans. The cells after it in the same row are shifted
+    /// to the right to make room.
+    ///
+    /// A span of zero is treated as a span of one.
+    #[must_use]
+    pub fn span(mut self, columns: usize) -> Self {
+        self.column_span = columns.max(1);
+        self
+    }
+
+    /// Set the number of rows the cell spans. Cells in the rows below it are shifted to the right
+    /// to make room.
+    ///
+    /// A span of zero is treated as a span of one.
+    #[must_use]
+    pub fn row_span(mut self, rows: usize) -> Self {
+        self.row_span = rows.max(1);
+        self
+    }
+
+    /// Set the horizontal alignment of the element inside the cell.
+    #[must_use]
+    pub fn align_x(mut self, align_x: impl Into<Horizontal>) -> Self {
+        self.align_x = align_x.into();
+        self
+    }
+
+    /// Set the vertical alignment of the element inside the cell.
+    #[must_use]
+    pub fn align_y(mut self, align_y: impl Into<Vertical>) -> Self {
+        self.align_y = align_y.into();
+        self
+    }
+
+    /// Set the text content of the cell, which stands in for the element inside it when the grid
+    /// is exported with [`Grid::to_csv`] or [`Grid::to_tsv`], or when the cell is copied to the
+    /// clipboard.
+    #[must_use]
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Place the cell in the area of the grid with the given name, set with [`Grid::area`]. The
+    /// cell fills the whole area instead of taking the next free slot in its row, and the cells
+    /// after it are placed around it. A cell placed in an area that does not exist is placed as
+    /// usual.
+    #[must_use]
+    pub fn in_area(mut self, area: impl Into<String>) -> Self {
+        self.area = Some(area.into());
+        self
+    }
+
+    /// Set the function checking the values typed into the cell while it is being edited with
+    /// [`Grid::editing`], giving the error to show for an invalid value. While the value is
+    /// invalid, the cell is drawn with a red border, the error is shown in a tooltip over the
+    /// cell, and pressing Enter does not produce the message set with [`Grid::on_commit`].
+    #[must_use]
+    pub fn validator(mut self, validator: impl Fn(&str) -> Result<(), String> + 'a) -> Self {
+        self.validator = Some(Box::new(validator));
+        self
+    }
+}
+
+impl<'a, M: Clone + 'a, T, R> Cell<'a, M, T, R> {
+    /// Set the message to produce when the cell is pressed, without wrapping the element inside
+    /// it in a button. The cursor turns into a pointer over the cell. The message is produced
+    /// along with the message set with [`Grid::on_press`], if the cell is in the rows of the grid,
+    /// and also when Enter is pressed while the cell is focused.
+    #[must_use]
+    pub fn on_press(mut self, message: M) -> Self {
+        self.on_press = Some(Box::new(move || message.clone()));
+        self
+    }
+}
+
+impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a> Cell<'a, M, T, R> {
+    /// Create a new grid cell with nothing in it, which still takes up its slot and is drawn with
+    /// its style. The style of empty cells can be set for the whole grid with
+    /// [`Grid::placeholder_style`].
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            placeholder: true,
+            ..Self::from(Space::new(0, 0))
+        }
+    }
+}
+
+impl<'a, M: 'a, T: container::Catalog + 'a, R: advanced::text::Renderer + 'a> Cell<'a, M, T, R> {
+    /// Show the given tooltip at the given position while the cursor is over the element inside
+    /// the cell, like wrapping the element in a [`Tooltip`].
+    #[must_use]
+    pub fn tooltip(
+        mut self,
+        tooltip: impl Into<Element<'a, M, T, R>>,
+        position: tooltip::Position,
+    ) -> Self {
+        self.element = Tooltip::new(self.element, tooltip, position).into();
+        self
+    }
+}
+
+impl<'a, M: 'a, T: scrollable::Catalog + 'a, R: advanced::Renderer + 'a> Cell<'a, M, T, R> {
+    /// Set what happens to the element inside the cell when it is larger than the cell. See
+    /// [`Overflow`] for more information.
+    #[must_use]
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.clip = overflow == Overflow::Clip;
+        if overflow == Overflow::Scroll {
+            self.element = Scrollable::new(self.element)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into();
+        }
         self
     }
 }
 
+/// Layer one style on top of another. Each part of the style on top that is left unset, like a
+/// background of [`None`] or a border of zero width, lets the style below show through.
+fn layer(below: &Style, above: &Style) -> Style {
+    Style {
+        text_color: above.text_color.or(below.text_color),
+        background: above.background.or(below.background),
+        border: if above.border.width > 0.0 || above.border.radius != Radius::default() {
+            above.border
+        } else {
+            below.border
+        },
+        shadow: if above.shadow == Shadow::default() {
+            below.shadow
+        } else {
+            above.shadow
+        },
+    }
+}
+
 /// A factory for creating cells in a grid.
-pub struct Factory<'a, M, T, R>(Box<dyn Fn() -> Cell<'a, M, T, R> + 'a>);
+///
+/// Each time a cell is created, the factory is given the index of the row the cell is in, and the
+/// index of the cell within that row.
+///
+/// Factories are reference-counted, so cloning one is cheap and the clone creates the same cells.
+pub struct Factory<'a, M, T, R>(Source<'a, M, T, R>);
+
+/// How a [`Factory`] creates its cells. Factories of elements are kept apart from factories of
+/// whole cells, so that creating a cell from an element only clones the element rather than
+/// calling a function that creates the cell around it.
+enum Source<'a, M, T, R> {
+    /// An element cloned into each cell, with the default style.
+    Element(Rc<dyn Fn() -> Element<'a, M, T, R> + 'a>),
+    /// An element cloned into each cell, with the given style.
+    Styled(Rc<dyn Fn() -> Element<'a, M, T, R> + 'a>, Rc<Style>),
+    /// A function creating each cell, given its row and column.
+    Indexed(Rc<dyn Fn(usize, usize) -> Cell<'a, M, T, R> + 'a>),
+}
+
+impl<M, T, R> fmt::Debug for Factory<'_, M, T, R> {
+    /// Format the factory, which is opaque.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Factory").finish_non_exhaustive()
+    }
+}
+
+impl<M, T, R> Clone for Factory<'_, M, T, R> {
+    fn clone(&self) -> Self {
+        Self(match &self.0 {
+            Source::Element(element) => Source::Element(Rc::clone(element)),
+            Source::Styled(element, style) => Source::Styled(Rc::clone(element), Rc::clone(style)),
+            Source::Indexed(factory) => Source::Indexed(Rc::clone(factory)),
+        })
+    }
+}
 
 impl<'a, M, T, R> Factory<'a, M, T, R> {
     /// Create a new factory that creates a grid cell with an element in it, with the default style.
@@ -39,9 +349,7 @@ impl<'a, M, T, R> Factory<'a, M, T, R> {
     /// The [`Clone`] constraint is necessary because the factory creates owned elements for `iced`
     /// to consume on each `view` cycle.
     pub fn from_element<E: Into<Element<'a, M, T, R>> + Clone + 'a>(element: E) -> Self {
-        Self(Box::new(move || {
-            Cell(element.clone().into(), Style::default())
-        }))
+        Self(Source::Element(Rc::new(move || element.clone().into())))
     }
 
     /// Create a new factory that creates a grid cell with an element in it, with the given style.
@@ -51,12 +359,73 @@ impl<'a, M, T, R> Factory<'a, M, T, R> {
         element: E,
         style: Style,
     ) -> Self {
-        Self(Box::new(move || Cell(element.clone().into(), style)))
+        Self(Source::Styled(
+            Rc::new(move || element.clone().into()),
+            Rc::new(style),
+        ))
     }
 
     /// Create a new factory from the given function.
     pub fn from_factory<F: Fn() -> Cell<'a, M, T, R> + 'a>(factory: F) -> Self {
-        Self(Box::new(factory))
+        Self(Source::Indexed(Rc::new(move |_, _| factory())))
+    }
+
+    /// Create a new factory from the given function, which is called with the row and column of
+    /// the cell being created.
+    pub fn from_indexed<F: Fn(usize, usize) -> Cell<'a, M, T, R> + 'a>(factory: F) -> Self {
+        Self(Source::Indexed(Rc::new(factory)))
+    }
+}
+
+impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a> Factory<'a, M, T, R> {
+    /// Create a new factory that creates empty grid cells. See [`Cell::empty`] for more
+    /// information.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self::from_factory(Cell::empty)
+    }
+
+    /// Create the cell at the given row and column.
+    fn create(&self, row: usize, column: usize) -> Cell<'a, M, T, R> {
+        match &self.0 {
+            Source::Element(element) => Cell::from(element()),
+            Source::Styled(element, style) => Cell::from(element()).style(**style),
+            Source::Indexed(factory) => factory(row, column),
+        }
+    }
+}
+
+#[cfg(feature = "cache")]
+impl<M: 'static, T: 'static, R: advanced::Renderer + 'static> Factory<'static, M, T, R> {
+    /// Create a new factory from the given function, which is called with the row and column of
+    /// the cell being created, keeping the element it creates between `view` cycles. The function
+    /// is only called again once the row, the column, or the given version of the cell changes,
+    /// so cells that stay the same are not created again on each `view` cycle.
+    ///
+    /// The version can be anything that can be hashed. Cached cells can also be invalidated on the
+    /// grid, with [`Grid::invalidate`], [`Grid::invalidate_row`], and [`Grid::invalidate_all`].
+    ///
+    /// The cell has the default style, but it can still be styled by the grid, like with
+    /// [`Grid::style_fn`].
+    pub fn cached<V, E>(version: V, view: impl Fn(usize, usize) -> E + 'static) -> Self
+    where
+        V: std::hash::Hash + Clone + 'static,
+        E: Into<Element<'static, M, T, R>> + 'static,
+    {
+        let view = Rc::new(view);
+        Self(Source::Indexed(Rc::new(move |row, column| {
+            let view = Rc::clone(&view);
+            let version = version.clone();
+            let mut cell = Cell::from(Space::new(0, 0));
+            cell.cached = Some(Box::new(move |invalidated| {
+                iced::widget::lazy(
+                    (row, column, version, invalidated),
+                    move |&(row, column, _, _)| view(row, column),
+                )
+                .into()
+            }));
+            cell
+        })))
     }
 }
 
@@ -68,121 +437,3514 @@ impl<'a, M, T, R, E: Into<Element<'a, M, T, R>> + Clone + 'a> From<E> for Factor
     }
 }
 
-/// A grid of cells.
-pub struct Grid<'a, M: 'a, T: 'a = Theme, R: advanced::Renderer + 'a = iced::Renderer> {
-    rows: Vec<Vec<Factory<'a, M, T, R>>>,
-    cell_width: Pixels,
-    cell_height: Pixels,
-    gutter: Pixels,
-    padding: Pixels,
+/// A source of data for a [`Grid`], creating the cells of the grid from its rows and columns on
+/// demand rather than from rows of factories built up front.
+///
+/// A grid is created from a model with [`Grid::from_model`].
+pub trait GridModel<'a, M, T, R> {
+    /// Get the number of rows in the model.
+    fn rows(&self) -> usize;
+
+    /// Get the number of columns in the model.
+    fn columns(&self) -> usize;
+
+    /// Create the cell at the given row and column.
+    fn cell(&self, row: usize, column: usize) -> Cell<'a, M, T, R>;
 }
 
-impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a> Default for Grid<'a, M, T, R> {
-    /// Create a new grid with no rows, and zero width, height, gutter, and padding.
-    fn default() -> Self {
-        Self {
-            rows: Vec::new(),
-            cell_width: Pixels::default(),
-            cell_height: Pixels::default(),
-            gutter: Pixels::default(),
-            padding: Pixels::default(),
+/// The direction in which cells flow when lines of cells are added to a [`Grid`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FlowDirection {
+    /// Each line of cells is a row, so cells flow from left to right and then from top to bottom.
+    #[default]
+    RowMajor,
+    /// Each line of cells is a column, so cells flow from top to bottom and then from left to right.
+    ColumnMajor,
+}
+
+/// The direction in which the columns of a [`Grid`] are laid out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Direction {
+    /// The first column is on the left, like in English.
+    #[default]
+    Ltr,
+    /// The first column is on the right, like in Arabic and Hebrew.
+    Rtl,
+}
+
+/// How the tracks of a [`Grid`] are spread across the space left over when they do not fill the
+/// grid, like `justify-content` and `align-content` in CSS.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Distribution {
+    /// The tracks are packed together at the start, leaving the space after them empty.
+    #[default]
+    Start,
+    /// The tracks are packed together in the middle, with as much space before them as after them.
+    Center,
+    /// The tracks are packed together at the end, leaving the space before them empty.
+    End,
+    /// The space is shared out evenly between the tracks, with the first and last tracks at the
+    /// edges of the grid.
+    SpaceBetween,
+    /// Each track gets an even share of the space, split in half on either side of it, so the
+    /// space at the edges of the grid is half as large as the space between the tracks.
+    SpaceAround,
+}
+
+impl Distribution {
+    /// Swap the start and the end, for the columns of a grid laid out from right to left.
+    const fn reversed(self) -> Self {
+        match self {
+            Self::Start => Self::End,
+            Self::End => Self::Start,
+            distribution => distribution,
         }
     }
 }
 
-impl<'a, M: 'a, T: 'a + container::Catalog, R: advanced::Renderer + 'a> From<&Grid<'a, M, T, R>>
-    for Element<'a, M, T, R>
-where
-    T::Class<'a>: From<StyleFn<'a, T>>,
-{
-    fn from(
-        Grid {
-            rows,
-            cell_width,
-            cell_height,
-            gutter,
-            padding,
-        }: &Grid<'a, M, T, R>,
-    ) -> Self {
-        Container::new(
-            rows.iter()
-                .map(|row| {
-                    row.iter()
-                        .map(|column| {
-                            let Cell(element, style) = column.0();
-                            Container::new(element)
-                                .center_x(*cell_width)
-                                .center_y(*cell_height)
-                                .style(move |_| style)
-                                .into()
-                        })
-                        .collect::<Row<M, T, R>>()
-                        .spacing(*gutter)
-                        .into()
-                })
-                .collect::<Column<M, T, R>>()
-                .spacing(*gutter),
-        )
-        .padding(padding.0)
-        .into()
-    }
+/// How the rows of a [`Grid`] change when rows are added, removed, or moved, set with
+/// [`Grid::row_transition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowTransition {
+    /// Rows that are added fade in, while other rows jump to where they end up.
+    Fade,
+    /// Rows that are added slide down into place from above, and rows that end up somewhere
+    /// else, because rows before them were added or removed, slide from where they were.
+    Slide,
 }
 
-impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a> Grid<'a, M, T, R> {
-    /// Create a new grid with no rows, and zero width, height, gutter, and padding.
+/// How far pressing Tab moves the focused cell of a [`Grid`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Traversal {
+    /// Tab moves to the next cell in the row, and on to the first cell of the next row after the
+    /// last cell.
+    #[default]
+    Cell,
+    /// Tab moves to the cell in the same column of the next row.
+    Row,
+}
+
+/// What happens to the element inside a [`Cell`] when it is larger than the cell.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Overflow {
+    /// The element is drawn in full, over the cells next to it.
+    #[default]
+    Visible,
+    /// The element is cut off at the edges of the cell.
+    Clip,
+    /// The element is wrapped in a vertical [`Scrollable`], so it can be scrolled within the cell.
+    Scroll,
+}
+
+/// What a [`Grid`] does with rows that have fewer or more cells than other rows.
+///
+/// Rows without any cells, like rows outside the view of a virtualized grid, are left alone, and
+/// so is the header.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RaggedRows {
+    /// Rows are laid out as they are, leaving the slots after shorter rows empty.
+    #[default]
+    Keep,
+    /// Shorter rows are padded with [empty cells](Cell::empty), styled like the other cells in
+    /// their row, until they are as long as the longest row.
+    PadWithEmpty,
+    /// Longer rows are cut short, until they are as short as the shortest row.
+    Truncate,
+    /// Rows of different lengths are a bug, so the grid panics when it is turned into a widget.
+    Error,
+}
+
+/// A change to the selection of a [`Grid`], made by the user pressing cells.
+///
+/// Pressing a cell selects only that cell, pressing it while holding Ctrl (or Command on macOS)
+/// toggles whether it is selected, and pressing it while holding Shift selects the range of cells
+/// between it and the anchor. Holding both extends the selection with that range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectionChanged {
+    /// The full set of selected cells, as rows and columns.
+    pub selection: HashSet<(usize, usize)>,
+    /// The cell that ranges of cells are selected from, which should be given back to the grid with
+    /// [`Grid::selected`].
+    pub anchor: Option<(usize, usize)>,
+}
+
+/// A page of the rows of a [`Grid`], given as the index of the page and the number of rows on each
+/// page.
+///
+/// A grid showing a page with [`Grid::page`] only creates the cells of the rows on that page, while
+/// still giving them their indices among all of the rows of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pager {
+    /// The index of the page, starting from zero.
+    pub page: usize,
+    /// The number of rows on each page.
+    pub rows_per_page: usize,
+}
+
+impl Pager {
+    /// Create a new pager for the given page with the given number of rows on each page.
+    ///
+    /// A `rows_per_page` of zero is treated as one.
     #[must_use]
-    pub fn new() -> Self {
-        Self::default()
+    pub fn new(page: usize, rows_per_page: usize) -> Self {
+        Self {
+            page,
+            rows_per_page: rows_per_page.max(1),
+        }
     }
 
-    /// Add a row to the grid.
+    /// Find how many pages the given number of rows are split into. There is always at least one
+    /// page, even if there are no rows.
     #[must_use]
-    pub fn with_row<C: Into<Factory<'a, M, T, R>>>(
-        mut self,
-        row: impl IntoIterator<Item = C>,
-    ) -> Self {
-        self.rows.push(row.into_iter().map(Into::into).collect());
-        self
+    pub fn page_count(self, row_count: usize) -> usize {
+        row_count.div_ceil(self.rows_per_page.max(1)).max(1)
     }
 
-    /// Add multiple rows to the grid.
+    /// Find the range of rows on the page, out of the given number of rows. A page past the last
+    /// page has no rows.
     #[must_use]
-    pub fn with_rows<B: IntoIterator<Item = C>, C: Into<Factory<'a, M, T, R>>>(
-        mut self,
-        rows: impl IntoIterator<Item = B>,
-    ) -> Self {
-        self.rows.extend(
-            rows.into_iter()
-                .map(|row| row.into_iter().map(Into::into).collect()),
-        );
-        self
+    pub fn rows(self, row_count: usize) -> Range<usize> {
+        let start = self
+            .page
+            .saturating_mul(self.rows_per_page.max(1))
+            .min(row_count);
+        start..(start + self.rows_per_page.max(1)).min(row_count)
     }
+}
 
-    /// Set the width of each cell in the grid.
+/// The size of a row or column in the template of a [`Grid`], like a track size in a CSS grid
+/// template.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Track {
+    /// A track of a fixed size in pixels.
+    Px(f32),
+    /// A track sharing the space left over by the other tracks with the other fractional tracks,
+    /// taking the given fraction of it.
+    Fr(f32),
+    /// A track as large as its largest cell.
+    Auto,
+    /// A track sharing the space left over by the other tracks like a [`Track::Fr`] of one, but
+    /// never smaller than the first size or larger than the second size in pixels, like
+    /// `minmax(min, max)` in CSS.
+    MinMax(f32, f32),
+}
+
+impl Track {
+    /// Get the smallest and largest sizes the track is allowed to be.
+    const fn limits(self) -> (f32, f32) {
+        match self {
+            Self::MinMax(min, max) => (min, max),
+            Self::Px(_) | Self::Fr(_) | Self::Auto => UNLIMITED,
+        }
+    }
+}
+
+/// The limits of a track that can be any size.
+const UNLIMITED: (f32, f32) = (0.0, f32::INFINITY);
+
+/// The default longest time between two presses of the same cell for them to be a double press.
+const DOUBLE_PRESS_INTERVAL: Duration = Duration::from_millis(300);
+
+/// The number of portions of the left over space a track of one fraction takes, so that fractions
+/// are kept to a hundredth of a fraction.
+const FRACTION_PORTIONS: f32 = 100.0;
+
+/// How far the first cell of a row of a tree of rows is indented for each level it is nested at.
+const INDENT: f32 = 16.0;
+
+/// The width of the toggle for expanding and collapsing a row of a tree of rows, which rows
+/// without children leave empty.
+const EXPANDER_WIDTH: f32 = 20.0;
+
+/// The height of the skeleton cells shown in place of rows that are not loaded yet.
+const SKELETON_HEIGHT: f32 = 16.0;
+
+/// The style of the skeleton cells shown in place of rows that are not loaded yet, a translucent
+/// gray that shows on light and dark backgrounds alike.
+const SKELETON_STYLE: Style = Style {
+    text_color: None,
+    background: Some(Background::Color(Color::from_rgba(0.5, 0.5, 0.5, 0.2))),
+    border: Border {
+        color: Color::TRANSPARENT,
+        width: 0.0,
+        radius: Radius {
+            top_left: 4.0,
+            top_right: 4.0,
+            bottom_right: 4.0,
+            bottom_left: 4.0,
+        },
+    },
+    shadow: Shadow {
+        color: Color::TRANSPARENT,
+        offset: iced::Vector::ZERO,
+        blur_radius: 0.0,
+    },
+};
+
+/// The style layered on top of the style of a cell being edited while the value typed into it is
+/// rejected by the validator of the cell.
+const INVALID_STYLE: Style = Style {
+    text_color: None,
+    background: None,
+    border: Border {
+        color: Color::from_rgb(0.85, 0.2, 0.2),
+        width: 2.0,
+        radius: Radius {
+            top_left: 0.0,
+            top_right: 0.0,
+            bottom_right: 0.0,
+            bottom_left: 0.0,
+        },
+    },
+    shadow: Shadow {
+        color: Color::TRANSPARENT,
+        offset: iced::Vector::ZERO,
+        blur_radius: 0.0,
+    },
+};
+
+impl From<Track> for Length {
+    fn from(track: Track) -> Self {
+        match track {
+            Track::Px(size) => Self::Fixed(size),
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Track::Fr(fraction) => Self::FillPortion(
+                (fraction * FRACTION_PORTIONS)
+                    .round()
+                    .clamp(0.0, f32::from(u16::MAX)) as u16,
+            ),
+            Track::Auto => Self::Shrink,
+            Track::MinMax(..) => Track::Fr(1.0).into(),
+        }
+    }
+}
+
+/// The order the rows of a [`Grid`] are sorted in by one of its columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// The rows are sorted from the smallest key to the largest.
+    Ascending,
+    /// The rows are sorted from the largest key to the smallest.
+    Descending,
+}
+
+impl SortOrder {
+    /// Get the opposite order.
     #[must_use]
-    pub fn cell_width(mut self, cell_width: impl Into<Pixels>) -> Self {
-        self.cell_width = cell_width.into();
-        self
+    pub const fn reversed(self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
     }
+}
 
-    /// Set the height of each cell in the grid.
+/// A way of combining the values of the rows in a column of a [`Grid`] into one value, shown in
+/// the footer with [`Grid::aggregate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    /// The sum of the values.
+    Sum,
+    /// The number of values.
+    Count,
+    /// The mean of the values, which there is none of without any values.
+    Avg,
+}
+
+impl Aggregate {
+    /// Combine the given values into one, or nothing for the mean of no values.
     #[must_use]
-    pub fn cell_height(mut self, cell_height: impl Into<Pixels>) -> Self {
-        self.cell_height = cell_height.into();
-        self
+    pub fn apply(self, values: impl IntoIterator<Item = f64>) -> Option<f64> {
+        let (count, sum) = values
+            .into_iter()
+            .fold((0_u32, 0.0), |(count, sum), value| (count + 1, sum + value));
+        match self {
+            Self::Sum => Some(sum),
+            Self::Count => Some(f64::from(count)),
+            Self::Avg => (count > 0).then(|| sum / f64::from(count)),
+        }
     }
 
-    /// Set the size of the gutter between rows and columns in the grid.
+    /// Get the format the aggregate is shown in by default, which is a whole number for a count
+    /// and a number with two digits after the decimal point otherwise.
+    #[must_use]
+    pub const fn format(self) -> CellFormat {
+        match self {
+            Self::Count => CellFormat::number(0),
+            Self::Sum | Self::Avg => CellFormat::number(2),
+        }
+    }
+}
+
+/// A function giving the value of a row of a grid in a column, if it has one, given the index of
+/// the row.
+type Accessor<'a> = Rc<dyn Fn(usize) -> Option<f64> + 'a>;
+
+/// A function comparing two rows of a grid, given their indices.
+type Comparator<'a> = Rc<dyn Fn(usize, usize) -> Ordering + 'a>;
+
+/// The definition of a column of a [`Grid`], with the title shown in the header above it and the
+/// key the rows of the grid can be sorted by.
+pub struct ColumnDef<'a, M, T, R> {
+    title: Factory<'a, M, T, R>,
+    sort_key: Option<Comparator<'a>>,
+    sort_order: Option<SortOrder>,
+}
+
+impl<M, T, R> fmt::Debug for ColumnDef<'_, M, T, R> {
+    /// Format the column definition, leaving out its title and sort key.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ColumnDef")
+            .field("sort_key", &self.sort_key.is_some())
+            .field("sort_order", &self.sort_order)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, M, T, R> ColumnDef<'a, M, T, R> {
+    /// Create a new column definition with the given title, which the rows cannot be sorted by.
+    pub fn new(title: impl Into<Factory<'a, M, T, R>>) -> Self {
+        Self {
+            title: title.into(),
+            sort_key: None,
+            sort_order: None,
+        }
+    }
+
+    /// Set the key the rows of the grid are sorted by when they are sorted by this column, given
+    /// the index of a row.
     #[must_use]
-    pub fn gutter(mut self, gutter: impl Into<Pixels>) -> Self {
-        self.gutter = gutter.into();
+    pub fn sort_key<K: Ord>(mut self, sort_key: impl Fn(usize) -> K + 'a) -> Self {
+        self.sort_key = Some(Rc::new(move |a, b| sort_key(a).cmp(&sort_key(b))));
         self
     }
 
-    /// Set the padding around the grid.
+    /// Set the order the rows of the grid are currently sorted in by this column, if they are
+    /// sorted by it.
     #[must_use]
-    pub fn padding(mut self, padding: impl Into<Pixels>) -> Self {
-        self.padding = padding.into();
+    pub const fn sort_order(mut self, sort_order: Option<SortOrder>) -> Self {
+        self.sort_order = sort_order;
         self
     }
 }
+
+/// A function creating the element a cell is swapped for while it is being edited, given the grid,
+/// the row and column of the cell and the error the validator of the cell gives for the value
+/// being edited, if any.
+type Editor<'a, M, T, R> =
+    fn(&Grid<'a, M, T, R>, usize, usize, Option<String>) -> Element<'a, M, T, R>;
+
+/// A function creating the element showing a label of a row or column, like the number of a row in
+/// a grid with row numbers.
+type Label<'a, M, T, R> = fn(String) -> Element<'a, M, T, R>;
+
+/// A function creating the row of controls for changing pages shown below the rows of a paginated
+/// grid.
+type PagerRow<'a, M, T, R> = fn(&Grid<'a, M, T, R>) -> Element<'a, M, T, R>;
+
+/// A function creating the toggle for expanding and collapsing the given row of a grid whose rows
+/// form a tree.
+type Expander<'a, M, T, R> = fn(&Grid<'a, M, T, R>, usize) -> Element<'a, M, T, R>;
+
+/// A factory for creating the cells of a row that is only called once the row is needed, given the
+/// index of the row.
+type RowFactory<'a, M, T, R> = Rc<dyn Fn(usize) -> Vec<Factory<'a, M, T, R>> + 'a>;
+
+/// A row that is only created once it is needed, given as the index its factory is called with,
+/// which is the index the row was added at and stays the same as rows are inserted and removed
+/// around it, and the factory.
+type LazyRow<'a, M, T, R> = (usize, RowFactory<'a, M, T, R>);
+
+/// A function producing the message for a block of cells selected by dragging, given the range of
+/// rows and the range of columns of the block.
+type OnRangeSelect<'a, M> = Rc<dyn Fn(RangeInclusive<usize>, RangeInclusive<usize>) -> M + 'a>;
+
+/// A function giving the style of the cell at the given row and column for the given theme.
+type CellStyleFn<'a, T> = Rc<dyn Fn(usize, usize, &T) -> Style + 'a>;
+
+/// A function giving the style of the frame of a grid for the given theme.
+type FrameStyleFn<'a, T> = Rc<dyn Fn(&T) -> style::Style + 'a>;
+
+/// A function producing the message for the cell under the cursor changing, given the row and
+/// column of the cell under the cursor, if any.
+type OnHover<'a, M> = Rc<dyn Fn(Option<(usize, usize)>) -> M + 'a>;
+
+/// A function producing the message for text pasted into the grid, given the row and column of
+/// the selected cell and the fields of each row of the text.
+type OnPaste<'a, M> = Rc<dyn Fn(usize, usize, Vec<Vec<String>>) -> M + 'a>;
+
+/// A function producing the message for a new value of the cell being edited, given the row and
+/// column of the cell and the new value.
+type OnEdit<'a, M> = Rc<dyn Fn(usize, usize, String) -> M + 'a>;
+
+/// A function creating the element drawn in each gutter between the rows or columns of a grid.
+type Separator<'a, M, T, R> = Rc<dyn Fn() -> Element<'a, M, T, R> + 'a>;
+
+/// A row of cells of an instance of a grid that is only there if the grid has it, like the header.
+type OptionalRow<'a, M, T, R> = Option<Vec<Placement<'a, M, T, R>>>;
+
+/// Add a record to delimited text like CSV, separating its fields with the given delimiter and
+/// quoting fields that contain the delimiter, quotes or line breaks.
+fn push_record<S: AsRef<str>>(
+    output: &mut String,
+    fields: impl IntoIterator<Item = S>,
+    delimiter: char,
+) {
+    for (index, field) in fields.into_iter().enumerate() {
+        let field = field.as_ref();
+        if index > 0 {
+            output.push(delimiter);
+        }
+        if field.contains([delimiter, '"', '\n', '\r']) {
+            output.push('"');
+            output.push_str(&field.replace('"', "\"\""));
+            output.push('"');
+        } else {
+            output.push_str(field);
+        }
+    }
+    output.push('\n');
+}
+
+/// Split delimited text like CSV into records of fields separated by the given delimiter,
+/// unquoting quoted fields. A line break at the end of the text does not start another record.
+fn parse_records(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '"' if quoted => {
+                if chars.next_if_eq(&'"').is_some() {
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            char if char == delimiter && !quoted => record.push(std::mem::take(&mut field)),
+            char => field.push(char),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+/// Lay out the cells of the given rows one after another in rows of the given number of cells,
+/// leaving the last row short if the cells run out before it is filled.
+fn reflowed<S>(rows: Vec<Vec<S>>, columns: usize) -> Vec<Vec<S>> {
+    let mut cells = rows.into_iter().flatten().peekable();
+    let mut rows = Vec::new();
+    while cells.peek().is_some() {
+        rows.push(cells.by_ref().take(columns.max(1)).collect());
+    }
+    rows
+}
+
+/// Find the name of the column at the given index like a spreadsheet names its columns, going from
+/// `A` to `Z`, then from `AA` to `ZZ`, and so on.
+fn column_name(column: usize) -> String {
+    let mut name = Vec::new();
+    let mut column = column + 1;
+    while column > 0 {
+        column -= 1;
+        name.push(char::from(
+            b'A' + u8::try_from(column % 26).unwrap_or_default(),
+        ));
+        column /= 26;
+    }
+    name.iter().rev().collect()
+}
+
+/// Swap the rows and columns of a grid of slots. Rows that are shorter than others leave empty
+/// slots behind.
+fn transposed<S>(rows: Vec<Vec<Option<S>>>) -> Vec<Vec<Option<S>>> {
+    let mut columns: Vec<Vec<_>> = Vec::new();
+    for (row, cells) in rows.into_iter().enumerate() {
+        for (column, cell) in cells.into_iter().enumerate() {
+            if cell.is_none() {
+                continue;
+            }
+            if columns.len() <= column {
+                columns.resize_with(column + 1, Vec::new);
+            }
+            columns[column].resize_with(row, || None);
+            columns[column].push(cell);
+        }
+    }
+    columns
+}
+
+/// Find where the row or column at each index ends up after a row or column is inserted at the
+/// given index.
+fn inserted(index: usize) -> impl Fn(usize) -> Option<usize> {
+    move |other| Some(if other < index { other } else { other + 1 })
+}
+
+/// Find where the row or column at each index ends up after the row or column at the given index
+/// is removed, which is nowhere for the removed one.
+fn removed(index: usize) -> impl Fn(usize) -> Option<usize> {
+    move |other| match other.cmp(&index) {
+        Ordering::Less => Some(other),
+        Ordering::Equal => None,
+        Ordering::Greater => Some(other - 1),
+    }
+}
+
+/// Swap the rows and columns of the given merged blocks, along with the cells they cover.
+fn transposed_merges(merges: Vec<Merge>) -> Vec<Merge> {
+    merges
+        .into_iter()
+        .map(|merge| Merge {
+            rows: merge.columns,
+            columns: merge.rows,
+        })
+        .collect()
+}
+
+/// A named block of rows and columns of a grid that cells can be placed in with [`Cell::in_area`].
+#[derive(Debug, Clone)]
+struct Area {
+    name: String,
+    rows: Range<usize>,
+    columns: Range<usize>,
+}
+
+/// A block of rows and columns of the body of a grid that is merged into a single cell with
+/// [`Grid::merge`].
+#[derive(Debug, Clone)]
+struct Merge {
+    rows: Range<usize>,
+    columns: Range<usize>,
+}
+
+impl Merge {
+    /// Check whether the block contains the cell at the given row and column.
+    fn contains(&self, row: usize, column: usize) -> bool {
+        self.rows.contains(&row) && self.columns.contains(&column)
+    }
+
+    /// Check whether the block overlaps the given block.
+    const fn overlaps(&self, other: &Self) -> bool {
+        self.rows.start < other.rows.end
+            && other.rows.start < self.rows.end
+            && self.columns.start < other.columns.end
+            && other.columns.start < self.columns.end
+    }
+}
+
+/// A grid of cells.
+///
+/// A grid turns into an [`Element`] like any other widget, so one grid can be nested in a cell of
+/// another with [`Cell::from`] or [`Factory::from`], and can span several rows and columns of
+/// the outer grid like any other cell. Presses on the cells of a nested grid are handled by the
+/// nested grid, so they are not also handled by the outer grid.
+///
+/// The overlays of the elements inside cells, like the menu of a pick list or combo box or a
+/// tooltip, are shown above the grid, unless the cell they belong to is scrolled out of view.
+pub struct Grid<'a, M: 'a, T: 'a = Theme, R: advanced::Renderer + 'a = iced::Renderer> {
+    rows: Vec<Vec<Option<Factory<'a, M, T, R>>>>,
+    column_capacity: usize,
+    wrap_columns: Option<usize>,
+    lazy_rows: Vec<Option<LazyRow<'a, M, T, R>>>,
+    row_keys: Vec<Option<u64>>,
+    header: Option<Vec<Option<Factory<'a, M, T, R>>>>,
+    footer: Option<Vec<Option<Factory<'a, M, T, R>>>>,
+    aggregates: Vec<(usize, Aggregate, Accessor<'a>, CellFormat)>,
+    aggregate_label: Option<Label<'a, M, T, R>>,
+    direction: FlowDirection,
+    auto_flow_dense: bool,
+    text_direction: Direction,
+    ragged_rows: RaggedRows,
+    cell_width: Length,
+    cell_height: Length,
+    aspect_ratio: Option<f32>,
+    column_widths: Vec<Length>,
+    row_heights: Vec<Length>,
+    column_limits: Vec<(f32, f32)>,
+    row_limits: Vec<(f32, f32)>,
+    areas: Vec<Area>,
+    merges: Vec<Merge>,
+    column_gutter: Pixels,
+    row_gutter: Pixels,
+    padding: Pixels,
+    id: Option<widget::Id>,
+    justify_content: Distribution,
+    align_content: Distribution,
+    selected: Option<(usize, usize)>,
+    selection: HashSet<(usize, usize)>,
+    selected_rows: HashSet<usize>,
+    selected_columns: HashSet<usize>,
+    on_row_select: Option<Rc<dyn Fn(usize) -> M + 'a>>,
+    on_column_select: Option<Rc<dyn Fn(usize) -> M + 'a>>,
+    selection_style: Option<Style>,
+    tab_traversal: Traversal,
+    focus_ring: Option<Border>,
+    on_select: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    on_selection_change: Option<Rc<dyn Fn(SelectionChanged) -> M + 'a>>,
+    on_focus_cell: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    on_row_reorder: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    on_column_reorder: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    on_column_resize: Option<Rc<dyn Fn(usize, Pixels) -> M + 'a>>,
+    editing: Option<(usize, usize)>,
+    edit_value: String,
+    editor: Option<Editor<'a, M, T, R>>,
+    on_edit: Option<OnEdit<'a, M>>,
+    on_commit: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    virtualized: Option<Pixels>,
+    scrollable: bool,
+    responsive: Option<Pixels>,
+    frozen_columns: usize,
+    row_numbers: Option<Label<'a, M, T, R>>,
+    column_letters: Option<Label<'a, M, T, R>>,
+    pager: Option<Pager>,
+    pager_row: Option<PagerRow<'a, M, T, R>>,
+    on_page_change: Option<Rc<dyn Fn(usize) -> M + 'a>>,
+    row_depth: Option<Rc<dyn Fn(usize) -> usize + 'a>>,
+    row_expanded: Option<Rc<dyn Fn(usize) -> bool + 'a>>,
+    on_toggle_row: Option<Rc<dyn Fn(usize, bool) -> M + 'a>>,
+    expander: Option<Expander<'a, M, T, R>>,
+    sort: Option<(usize, SortOrder)>,
+    sort_keys: Vec<Option<Comparator<'a>>>,
+    on_sort: Option<Rc<dyn Fn(usize, SortOrder) -> M + 'a>>,
+    row_filter: Option<Rc<dyn Fn(usize) -> bool + 'a>>,
+    on_press: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    on_double_press: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    on_row_press: Option<Rc<dyn Fn(usize) -> M + 'a>>,
+    skeleton_rows: usize,
+    on_request_rows: Option<Rc<dyn Fn(Range<usize>) -> M + 'a>>,
+    double_press_interval: Duration,
+    scroll_animation: Option<Duration>,
+    row_transition: Option<(RowTransition, Duration)>,
+    on_right_press: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    on_paint: Option<Rc<dyn Fn(usize, usize) -> M + 'a>>,
+    on_hover: Option<OnHover<'a, M>>,
+    on_range_select: Option<OnRangeSelect<'a, M>>,
+    on_paste: Option<OnPaste<'a, M>>,
+    header_height: Option<Length>,
+    header_style: Option<Style>,
+    placeholder_style: Option<Style>,
+    lines: Option<(f32, Color)>,
+    row_separator: Option<Separator<'a, M, T, R>>,
+    column_separator: Option<Separator<'a, M, T, R>>,
+    collapse_borders: bool,
+    corner_radius: f32,
+    stripes: Option<[Style; 2]>,
+    checkerboard: Option<[Style; 2]>,
+    row_style: Option<Rc<dyn Fn(usize) -> Style + 'a>>,
+    column_style: Option<Rc<dyn Fn(usize) -> Style + 'a>>,
+    style_fn: Option<CellStyleFn<'a, T>>,
+    rules: Vec<formatting::Rule<'a>>,
+    version: u64,
+    row_versions: HashMap<usize, u64>,
+    cell_versions: HashMap<(usize, usize), u64>,
+    frame_style: Option<FrameStyleFn<'a, T>>,
+    instance: RefCell<Option<Instance<'a, M, T, R>>>,
+}
+
+impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a> Clone for Grid<'a, M, T, R> {
+    /// Clone the grid. The factories of the cells are shared between the grid and its clone.
+    fn clone(&self) -> Self {
+        Self {
+            rows: self.rows.clone(),
+            column_capacity: self.column_capacity,
+            wrap_columns: self.wrap_columns,
+            lazy_rows: self.lazy_rows.clone(),
+            row_keys: self.row_keys.clone(),
+            header: self.header.clone(),
+            footer: self.footer.clone(),
+            aggregates: self.aggregates.clone(),
+            aggregate_label: self.aggregate_label,
+            direction: self.direction,
+            auto_flow_dense: self.auto_flow_dense,
+            text_direction: self.text_direction,
+            ragged_rows: self.ragged_rows,
+            cell_width: self.cell_width,
+            cell_height: self.cell_height,
+            aspect_ratio: self.aspect_ratio,
+            column_widths: self.column_widths.clone(),
+            row_heights: self.row_heights.clone(),
+            column_limits: self.column_limits.clone(),
+            row_limits: self.row_limits.clone(),
+            areas: self.areas.clone(),
+            merges: self.merges.clone(),
+            column_gutter: self.column_gutter,
+            row_gutter: self.row_gutter,
+            padding: self.padding,
+            id: self.id.clone(),
+            justify_content: self.justify_content,
+            align_content: self.align_content,
+            selected: self.selected,
+            selection: self.selection.clone(),
+            selected_rows: self.selected_rows.clone(),
+            selected_columns: self.selected_columns.clone(),
+            on_row_select: self.on_row_select.clone(),
+            on_column_select: self.on_column_select.clone(),
+            selection_style: self.selection_style,
+            tab_traversal: self.tab_traversal,
+            focus_ring: self.focus_ring,
+            on_select: self.on_select.clone(),
+            on_selection_change: self.on_selection_change.clone(),
+            on_focus_cell: self.on_focus_cell.clone(),
+            on_row_reorder: self.on_row_reorder.clone(),
+            on_column_reorder: self.on_column_reorder.clone(),
+            on_column_resize: self.on_column_resize.clone(),
+            editing: self.editing,
+            edit_value: self.edit_value.clone(),
+            editor: self.editor,
+            on_edit: self.on_edit.clone(),
+            on_commit: self.on_commit.clone(),
+            virtualized: self.virtualized,
+            scrollable: self.scrollable,
+            responsive: self.responsive,
+            frozen_columns: self.frozen_columns,
+            row_numbers: self.row_numbers,
+            column_letters: self.column_letters,
+            pager: self.pager,
+            pager_row: self.pager_row,
+            on_page_change: self.on_page_change.clone(),
+            row_depth: self.row_depth.clone(),
+            row_expanded: self.row_expanded.clone(),
+            on_toggle_row: self.on_toggle_row.clone(),
+            expander: self.expander,
+            sort: self.sort,
+            sort_keys: self.sort_keys.clone(),
+            on_sort: self.on_sort.clone(),
+            row_filter: self.row_filter.clone(),
+            on_press: self.on_press.clone(),
+            on_double_press: self.on_double_press.clone(),
+            on_row_press: self.on_row_press.clone(),
+            skeleton_rows: self.skeleton_rows,
+            on_request_rows: self.on_request_rows.clone(),
+            double_press_interval: self.double_press_interval,
+            scroll_animation: self.scroll_animation,
+            row_transition: self.row_transition,
+            on_right_press: self.on_right_press.clone(),
+            on_paint: self.on_paint.clone(),
+            on_hover: self.on_hover.clone(),
+            on_range_select: self.on_range_select.clone(),
+            on_paste: self.on_paste.clone(),
+            header_height: self.header_height,
+            header_style: self.header_style,
+            placeholder_style: self.placeholder_style,
+            lines: self.lines,
+            row_separator: self.row_separator.clone(),
+            column_separator: self.column_separator.clone(),
+            collapse_borders: self.collapse_borders,
+            corner_radius: self.corner_radius,
+            stripes: self.stripes,
+            checkerboard: self.checkerboard,
+            row_style: self.row_style.clone(),
+            column_style: self.column_style.clone(),
+            style_fn: self.style_fn.clone(),
+            rules: self.rules.clone(),
+            version: self.version,
+            row_versions: self.row_versions.clone(),
+            cell_versions: self.cell_versions.clone(),
+            frame_style: self.frame_style.clone(),
+            instance: RefCell::new(None),
+        }
+    }
+}
+
+impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a> fmt::Debug for Grid<'a, M, T, R> {
+    /// Format the structure of the grid: how many rows, columns, and cells it has, and how it is
+    /// sized, leaving out the factories of the cells.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Grid")
+            .field("header", &self.header.as_ref().map(Vec::len))
+            .field("footer", &self.footer.as_ref().map(Vec::len))
+            .field("aggregates", &self.aggregates.len())
+            .field("rows", &self.rows.len())
+            .field("column_capacity", &self.column_capacity)
+            .field("wrap_columns", &self.wrap_columns)
+            .field("lazy_rows", &self.lazy_rows.iter().flatten().count())
+            .field("keyed_rows", &self.row_keys.iter().flatten().count())
+            .field(
+                "columns",
+                &self.rows.iter().map(Vec::len).max().unwrap_or_default(),
+            )
+            .field("cells", &self.rows.iter().flatten().flatten().count())
+            .field("direction", &self.direction)
+            .field("auto_flow_dense", &self.auto_flow_dense)
+            .field("text_direction", &self.text_direction)
+            .field("ragged_rows", &self.ragged_rows)
+            .field("cell_width", &self.cell_width)
+            .field("cell_height", &self.cell_height)
+            .field("aspect_ratio", &self.aspect_ratio)
+            .field("column_widths", &self.column_widths)
+            .field("row_heights", &self.row_heights)
+            .field("column_limits", &self.column_limits)
+            .field("row_limits", &self.row_limits)
+            .field("areas", &self.areas)
+            .field("merges", &self.merges)
+            .field("column_gutter", &self.column_gutter)
+            .field("row_gutter", &self.row_gutter)
+            .field("padding", &self.padding)
+            .field("id", &self.id)
+            .field("justify_content", &self.justify_content)
+            .field("align_content", &self.align_content)
+            .field("selected", &self.selected)
+            .field("selection", &self.selection)
+            .field("selected_rows", &self.selected_rows)
+            .field("selected_columns", &self.selected_columns)
+            .field("on_row_select", &self.on_row_select.is_some())
+            .field("on_column_select", &self.on_column_select.is_some())
+            .field("selection_style", &self.selection_style)
+            .field("tab_traversal", &self.tab_traversal)
+            .field("focus_ring", &self.focus_ring)
+            .field("on_press", &self.on_press.is_some())
+            .field("on_double_press", &self.on_double_press.is_some())
+            .field("double_press_interval", &self.double_press_interval)
+            .field("scroll_animation", &self.scroll_animation)
+            .field("row_transition", &self.row_transition)
+            .field("on_row_press", &self.on_row_press.is_some())
+            .field("skeleton_rows", &self.skeleton_rows)
+            .field("on_request_rows", &self.on_request_rows.is_some())
+            .field("on_select", &self.on_select.is_some())
+            .field("on_selection_change", &self.on_selection_change.is_some())
+            .field("on_focus_cell", &self.on_focus_cell.is_some())
+            .field("on_row_reorder", &self.on_row_reorder.is_some())
+            .field("on_column_reorder", &self.on_column_reorder.is_some())
+            .field("on_column_resize", &self.on_column_resize.is_some())
+            .field("editing", &self.editing)
+            .field("edit_value", &self.edit_value)
+            .field("on_edit", &self.on_edit.is_some())
+            .field("on_commit", &self.on_commit.is_some())
+            .field("virtualized", &self.virtualized)
+            .field("scrollable", &self.scrollable)
+            .field("responsive", &self.responsive)
+            .field("frozen_columns", &self.frozen_columns)
+            .field("row_numbers", &self.row_numbers.is_some())
+            .field("column_letters", &self.column_letters.is_some())
+            .field("pager", &self.pager)
+            .field("on_page_change", &self.on_page_change.is_some())
+            .field("row_depth", &self.row_depth.is_some())
+            .field("row_expanded", &self.row_expanded.is_some())
+            .field("on_toggle_row", &self.on_toggle_row.is_some())
+            .field("sort", &self.sort)
+            .field("on_sort", &self.on_sort.is_some())
+            .field("on_right_press", &self.on_right_press.is_some())
+            .field("on_paint", &self.on_paint.is_some())
+            .field("on_hover", &self.on_hover.is_some())
+            .field("on_range_select", &self.on_range_select.is_some())
+            .field("on_paste", &self.on_paste.is_some())
+            .field("row_filter", &self.row_filter.is_some())
+            .field("header_height", &self.header_height)
+            .field("header_style", &self.header_style)
+            .field("placeholder_style", &self.placeholder_style)
+            .field("lines", &self.lines)
+            .field("row_separator", &self.row_separator.is_some())
+            .field("column_separator", &self.column_separator.is_some())
+            .field("collapse_borders", &self.collapse_borders)
+            .field("corner_radius", &self.corner_radius)
+            .field("stripes", &self.stripes)
+            .field("checkerboard", &self.checkerboard)
+            .field("row_style", &self.row_style.is_some())
+            .field("column_style", &self.column_style.is_some())
+            .field("style_fn", &self.style_fn.is_some())
+            .field("rules", &self.rules)
+            .field("version", &self.version)
+            .field("row_versions", &self.row_versions)
+            .field("cell_versions", &self.cell_versions)
+            .field("frame_style", &self.frame_style.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a> Default for Grid<'a, M, T, R> {
+    /// Create a new grid with no rows, and zero width, height, gutter, and padding.
+    fn default() -> Self {
+        Self {
+            rows: Vec::new(),
+            column_capacity: 0,
+            wrap_columns: None,
+            lazy_rows: Vec::new(),
+            row_keys: Vec::new(),
+            header: None,
+            footer: None,
+            aggregates: Vec::new(),
+            aggregate_label: None,
+            direction: FlowDirection::default(),
+            auto_flow_dense: false,
+            text_direction: Direction::Ltr,
+            ragged_rows: RaggedRows::Keep,
+            cell_width: Length::Fixed(0.0),
+            cell_height: Length::Fixed(0.0),
+            aspect_ratio: None,
+            column_widths: Vec::new(),
+            row_heights: Vec::new(),
+            column_limits: Vec::new(),
+            row_limits: Vec::new(),
+            areas: Vec::new(),
+            merges: Vec::new(),
+            column_gutter: Pixels::default(),
+            row_gutter: Pixels::default(),
+            padding: Pixels::default(),
+            id: None,
+            justify_content: Distribution::Start,
+            align_content: Distribution::Start,
+            selected: None,
+            selection: HashSet::new(),
+            selected_rows: HashSet::new(),
+            selected_columns: HashSet::new(),
+            on_row_select: None,
+            on_column_select: None,
+            selection_style: None,
+            tab_traversal: Traversal::Cell,
+            focus_ring: None,
+            on_select: None,
+            on_selection_change: None,
+            on_focus_cell: None,
+            on_row_reorder: None,
+            on_column_reorder: None,
+            on_column_resize: None,
+            editing: None,
+            edit_value: String::new(),
+            editor: None,
+            on_edit: None,
+            on_commit: None,
+            virtualized: None,
+            scrollable: false,
+            responsive: None,
+            frozen_columns: 0,
+            row_numbers: None,
+            column_letters: None,
+            pager: None,
+            pager_row: None,
+            on_page_change: None,
+            row_depth: None,
+            row_expanded: None,
+            on_toggle_row: None,
+            expander: None,
+            sort: None,
+            sort_keys: Vec::new(),
+            on_sort: None,
+            row_filter: None,
+            on_press: None,
+            on_double_press: None,
+            on_row_press: None,
+            skeleton_rows: 0,
+            on_request_rows: None,
+            double_press_interval: DOUBLE_PRESS_INTERVAL,
+            scroll_animation: None,
+            row_transition: None,
+            on_right_press: None,
+            on_paint: None,
+            on_hover: None,
+            on_range_select: None,
+            on_paste: None,
+            header_height: None,
+            header_style: None,
+            placeholder_style: None,
+            lines: None,
+            row_separator: None,
+            column_separator: None,
+            collapse_borders: false,
+            corner_radius: 0.0,
+            stripes: None,
+            checkerboard: None,
+            row_style: None,
+            column_style: None,
+            style_fn: None,
+            rules: Vec::new(),
+            version: 0,
+            row_versions: HashMap::new(),
+            cell_versions: HashMap::new(),
+            frame_style: None,
+            instance: RefCell::new(None),
+        }
+    }
+}
+
+impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a> Index<(usize, usize)> for Grid<'a, M, T, R> {
+    type Output = Factory<'a, M, T, R>;
+
+    /// Get the factory of the cell at the given row and column.
+    ///
+    /// # Panics
+    /// Panics if there is no cell at the given row and column, including when the slot is empty.
+    /// See [`Grid::get`] for a fallible version.
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        self.get(row, column)
+            .expect("there should be a cell at the given row and column")
+    }
+}
+
+impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a> IndexMut<(usize, usize)> for Grid<'a, M, T, R> {
+    /// Get a mutable reference to the factory of the cell at the given row and column.
+    ///
+    /// # Panics
+    /// Panics if there is no cell at the given row and column, including when the slot is empty.
+    /// See [`Grid::get_mut`] for a fallible version.
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(row, column)
+            .expect("there should be a cell at the given row and column")
+    }
+}
+
+impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a, B, C> FromIterator<B> for Grid<'a, M, T, R>
+where
+    B: IntoIterator<Item = C>,
+    C: Into<Factory<'a, M, T, R>>,
+{
+    /// Create a grid from rows of cells, like [`Grid::with_rows`] on a new grid.
+    fn from_iter<I: IntoIterator<Item = B>>(rows: I) -> Self {
+        Self::new().with_rows(rows)
+    }
+}
+
+impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a, B, C> Extend<B> for Grid<'a, M, T, R>
+where
+    B: IntoIterator<Item = C>,
+    C: Into<Factory<'a, M, T, R>>,
+{
+    /// Add rows of cells to the grid, or columns if the grid flows in
+    /// [`FlowDirection::ColumnMajor`] order, like [`Grid::with_rows`].
+    fn extend<I: IntoIterator<Item = B>>(&mut self, rows: I) {
+        for row in rows {
+            self.push_line(row);
+        }
+    }
+}
+
+impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a> From<&Grid<'a, M, T, R>>
+    for Element<'a, M, T, R>
+{
+    fn from(grid: &Grid<'a, M, T, R>) -> Self {
+        if grid.virtualized.is_some() || grid.responsive.is_some() {
+            Self::new(grid.clone())
+        } else {
+            grid.instantiate(grid.page_rows(), None).into()
+        }
+    }
+}
+
+impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a> From<Grid<'a, M, T, R>>
+    for Element<'a, M, T, R>
+{
+    /// Turn a grid into an element by value, so it can be created inside `view` like any other
+    /// widget.
+    fn from(grid: Grid<'a, M, T, R>) -> Self {
+        Self::new(grid)
+    }
+}
+
+impl<'a, M: 'a, T: 'a, R: advanced::Renderer + 'a> Grid<'a, M, T, R> {
+    /// Create a new grid with no rows, and zero width, height, gutter, and padding.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new grid like [`Self::new`], with room for the given number of rows of the given
+    /// number of cells, so that adding them does not have to grow the grid along the way.
+    #[must_use]
+    pub fn with_capacity(rows: usize, columns: usize) -> Self {
+        Self {
+            rows: Vec::with_capacity(rows),
+            column_capacity: columns,
+            ..Self::default()
+        }
+    }
+
+    /// Layer the styles the grid gives to the cell at the given row and column around the style of
+    /// the cell: the styles of the grid, its row, its column, its square and its stripe beneath it,
+    /// and the style of the selection on top of it if the cell, its row, or its column is
+    /// selected. Empty cells take the style the grid gives to placeholders instead of their own style.
+    fn styled(&self, row: usize, column: usize, mut cell: Cell<'a, M, T, R>) -> Cell<'a, M, T, R> {
+        if cell.placeholder
+            && let Some(placeholder_style) = self.placeholder_style
+        {
+            cell.style = Box::new(move |_| placeholder_style);
+        }
+        if let Some(style_fn) = self.style_fn.clone() {
+            let style = cell.style;
+            cell.style = Box::new(move |theme| layer(&style_fn(row, column, theme), &style(theme)));
+        }
+        if let Some(row_style) = &self.row_style {
+            let row_style = row_style(row);
+            let style = cell.style;
+            cell.style = Box::new(move |theme| layer(&row_style, &style(theme)));
+        }
+        if let Some(column_style) = &self.column_style {
+            let column_style = column_style(column);
+            let style = cell.style;
+            cell.style = Box::new(move |theme| layer(&column_style, &style(theme)));
+        }
+        if let Some(squares) = self.checkerboard {
+            let square = squares[(row + column) % 2];
+            let style = cell.style;
+            cell.style = Box::new(move |theme| layer(&square, &style(theme)));
+        }
+        if let Some(stripes) = self.stripes {
+            let stripe = stripes[row % 2];
+            let style = cell.style;
+            cell.style = Box::new(move |theme| layer(&stripe, &style(theme)));
+        }
+        for rule in self.rules.iter().filter(|rule| rule.matches(row, column)) {
+            let rule_style = rule.style_of();
+            let style = cell.style;
+            cell.style = Box::new(move |theme| layer(&style(theme), &rule_style));
+            if rule.stops() {
+                break;
+            }
+        }
+        if let Some(selection_style) = self.selection_style
+            && (self.selected == Some((row, column))
+                || self.selection.contains(&(row, column))
+                || self.selected_rows.contains(&row)
+                || self.selected_columns.contains(&column))
+        {
+            let style = cell.style;
+            cell.style = Box::new(move |theme| layer(&style(theme), &selection_style));
+        }
+        cell
+    }
+
+    /// Layer the style the grid gives to the cells of the header beneath the style of a cell.
+    fn styled_header(&self, mut cell: Cell<'a, M, T, R>) -> Cell<'a, M, T, R> {
+        if let Some(header_style) = self.header_style {
+            let style = cell.style;
+            cell.style = Box::new(move |theme| layer(&header_style, &style(theme)));
+        }
+        cell
+    }
+
+    /// Get the factories of the cells of the row at the given index, calling the factory of the
+    /// row if it is a lazy row.
+    fn factories(&self, row: usize) -> Cow<'_, [Option<Factory<'a, M, T, R>>]> {
+        if let Some(Some((index, factory))) = self.lazy_rows.get(row) {
+            Cow::Owned(factory(*index).into_iter().map(Some).collect())
+        } else if row >= self.rows.len() {
+            Cow::Owned(self.skeleton_row())
+        } else {
+            Cow::Borrowed(&self.rows[row])
+        }
+    }
+
+    /// Find the range of indices of the skeleton rows set with [`Self::on_request_rows`], which
+    /// always follow the last row of the grid.
+    const fn loading_rows(&self) -> Range<usize> {
+        self.rows.len()..self.rows.len() + self.skeleton_rows
+    }
+
+    /// Get the factories of the cells of a skeleton row, which has a cell in every column.
+    fn skeleton_row(&self) -> Vec<Option<Factory<'a, M, T, R>>> {
+        let skeleton = Factory::from_factory(|| {
+            Cell::from(Space::new(Length::Fill, SKELETON_HEIGHT)).style(SKELETON_STYLE)
+        });
+        vec![Some(skeleton); self.column_count_for(None).max(1)]
+    }
+
+    /// Create the cell at the given row and column with the given factory, giving a cached cell
+    /// the version the grid gives it so that invalidating it creates it again.
+    fn create(
+        &self,
+        factory: &Factory<'a, M, T, R>,
+        row: usize,
+        column: usize,
+    ) -> Cell<'a, M, T, R> {
+        let mut cell = factory.create(row, column);
+        if let Some(cached) = cell.cached.take() {
+            let version = self.version
+                + self.row_versions.get(&row).unwrap_or(&0)
+                + self.cell_versions.get(&(row, column)).unwrap_or(&0);
+            cell.element = cached(version);
+        }
+        cell
+    }
+
+    /// Create the cell of the header or the footer in the given column with the given factory.
+    /// These cells are in none of the rows of the grid, so the factory is given a row of zero and
+    /// a cached cell is only given the version of the whole grid, not the versions of the first row
+    /// and its cells.
+    fn create_outside(&self, factory: &Factory<'a, M, T, R>, column: usize) -> Cell<'a, M, T, R> {
+        let mut cell = factory.create(0, column);
+        if let Some(cached) = cell.cached.take() {
+            cell.element = cached(self.version);
+        }
+        cell
+    }
+
+    /// Create the cells of the body row at the given index, which has no cells if it is not
+    /// visible.
+    ///
+    /// The cell at the top-left corner of a merged block spans the whole block, and is an empty
+    /// cell if it has no factory, while the other cells of the block are not created.
+    fn create_row(&self, row: usize, visible: bool) -> Vec<Placement<'a, M, T, R>> {
+        if !visible {
+            return Vec::new();
+        }
+        self.factories(row)
+            .iter()
+            .enumerate()
+            .map(|(column, factory)| {
+                let merge = self.merge_at(row, column);
+                if merge
+                    .is_some_and(|merge| (merge.rows.start, merge.columns.start) != (row, column))
+                    || (merge.is_none() && factory.is_none())
+                {
+                    return None;
+                }
+                let cell = factory
+                    .as_ref()
+                    .map_or_else(Cell::empty, |factory| self.create(factory, row, column));
+                let mut cell = self.styled(row, column, cell);
+                if let Some(editor) = self.editor
+                    && self.editing == Some((row, column))
+                {
+                    let error = (cell.validator.as_ref())
+                        .and_then(|validator| validator(&self.edit_value).err());
+                    if error.is_some() {
+                        let style = cell.style;
+                        cell.style = Box::new(move |theme| layer(&style(theme), &INVALID_STYLE));
+                    }
+                    cell.element = editor(self, row, column, error);
+                }
+                if column == 0 {
+                    cell.element = self.nest(row, cell.element);
+                }
+                if let Some(merge) = merge {
+                    cell.row_span = merge.rows.len();
+                    cell.column_span = merge.columns.len();
+                }
+                Some((Part::Body(row, column), cell))
+            })
+            .collect()
+    }
+
+    /// Indent the given element of the first cell of the given row by how deeply the row is nested
+    /// in the tree of rows, after the toggle for expanding and collapsing the row if it has
+    /// children. Elements of grids whose rows do not form a tree are left alone.
+    fn nest(&self, row: usize, element: Element<'a, M, T, R>) -> Element<'a, M, T, R> {
+        let (Some(row_depth), Some(expander)) = (&self.row_depth, self.expander) else {
+            return element;
+        };
+        if row >= self.rows.len() {
+            return element;
+        }
+        let toggle = if self.has_children(row) {
+            expander(self, row)
+        } else {
+            Space::with_width(EXPANDER_WIDTH).into()
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let indent = row_depth(row) as f32 * INDENT;
+        Row::new()
+            .push(Space::with_width(indent))
+            .push(toggle)
+            .push(element)
+            .align_y(Alignment::Center)
+            .into()
+    }
+
+    /// Check whether the given row has children in the tree of rows, which are the rows right
+    /// after it that are nested more deeply than it.
+    fn has_children(&self, row: usize) -> bool {
+        self.row_depth.as_ref().is_some_and(|row_depth| {
+            row + 1 < self.rows.len() && row_depth(row + 1) > row_depth(row)
+        })
+    }
+
+    /// Check whether the given row of a tree of rows is expanded, showing its children.
+    fn is_expanded(&self, row: usize) -> bool {
+        self.row_expanded
+            .as_ref()
+            .is_none_or(|row_expanded| row_expanded(row))
+    }
+
+    /// Create the cells of the given rows of the body, shown at the given positions, evened out
+    /// and without the cells covered by merged blocks. Rows at positions outside the given window
+    /// have no cells.
+    fn create_body(
+        &self,
+        positions: Range<usize>,
+        body_rows: &[usize],
+        window: &Range<usize>,
+    ) -> Vec<Vec<Placement<'a, M, T, R>>> {
+        let mut body: Vec<_> = positions
+            .zip(body_rows)
+            .map(|(position, &row)| self.create_row(row, window.contains(&position)))
+            .collect();
+        self.even_out(&mut body, body_rows);
+        self.suppress_merged(&mut body, body_rows);
+        body
+    }
+
+    /// Find the merged block containing the cell at the given row and column, if there is one.
+    fn merge_at(&self, row: usize, column: usize) -> Option<&Merge> {
+        self.merges.iter().find(|merge| merge.contains(row, column))
+    }
+
+    /// Take the cells that are covered by the cell at the top-left corner of a merged block out
+    /// of the given created rows of the body, which show the given rows, so that they do not take
+    /// up slots of their own.
+    fn suppress_merged(&self, body: &mut [Vec<Placement<'a, M, T, R>>], body_rows: &[usize]) {
+        if self.merges.is_empty() {
+            return;
+        }
+        for (cells, &row) in body.iter_mut().zip(body_rows) {
+            let mut column = 0;
+            cells.retain(|_| {
+                let merged = self
+                    .merge_at(row, column)
+                    .is_some_and(|merge| (merge.rows.start, merge.columns.start) != (row, column));
+                column += 1;
+                !merged
+            });
+        }
+    }
+
+    /// Pad or cut the given created rows of the body, which show the given rows, so that they are
+    /// as long as each other, following the policy of the grid for ragged rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the policy is [`RaggedRows::Error`] and the rows are not as long as each other.
+    fn even_out(&self, body: &mut [Vec<Placement<'a, M, T, R>>], body_rows: &[usize]) {
+        let lengths: Vec<_> = self
+            .rows
+            .iter()
+            .map(Vec::len)
+            .enumerate()
+            .chain(body_rows.iter().copied().zip(body.iter().map(Vec::len)))
+            .filter(|&(_, length)| length > 0)
+            .collect();
+        let shortest = lengths.iter().map(|&(_, length)| length).min().unwrap_or(0);
+        let longest = lengths.iter().map(|&(_, length)| length).max().unwrap_or(0);
+        match self.ragged_rows {
+            RaggedRows::Keep => {}
+            RaggedRows::PadWithEmpty => {
+                for (cells, &row) in body.iter_mut().zip(body_rows) {
+                    if cells.is_empty() {
+                        continue;
+                    }
+                    for column in cells.len()..longest {
+                        let cell = self.styled(row, column, Cell::empty());
+                        cells.push(Some((Part::Body(row, column), cell)));
+                    }
+                }
+            }
+            RaggedRows::Truncate => {
+                for cells in body {
+                    cells.truncate(shortest);
+                }
+            }
+            RaggedRows::Error => {
+                if let Some(&(row, length)) = lengths.iter().find(|&&(_, length)| length != longest)
+                {
+                    panic!("row {row} of the grid has {length} cells instead of {longest}");
+                }
+            }
+        }
+    }
+
+    /// Call the factory of every cell in the given window of rows and place the cells, ready for
+    /// the grid to be laid out and drawn. The window is given as positions of the rows in the
+    /// order they are shown in, and rows outside of it are left empty.
+    ///
+    /// If a number of columns to reflow the cells into is given, the cells of the rows are laid
+    /// out one after another in rows of that many cells instead.
+    fn instantiate(&self, window: Range<usize>, reflow: Option<usize>) -> Instance<'a, M, T, R> {
+        let numbers = self.row_numbers.filter(|_| reflow.is_none());
+        let column_count = self.column_count_for(reflow);
+        let (letters, header) = self.create_top(column_count, numbers.is_some(), reflow);
+        let page = self.page_rows();
+        let body_rows = self.row_order()[page.clone()].to_vec();
+        let mut body = self.create_body(page.clone(), &body_rows, &window);
+        if let Some(number) = numbers {
+            self.number(&mut body, &body_rows, number);
+        }
+        let (body, body_rows) = match reflow {
+            Some(columns) => (reflowed(body, columns), Vec::new()),
+            None => (body, body_rows),
+        };
+        let header_rows = self.header_rows(reflow);
+        let number_columns = usize::from(numbers.is_some());
+        let column_count = column_count + number_columns;
+        let footer = self.create_footer(numbers.is_some());
+        let footer_rows = usize::from(footer.is_some());
+        let pager = self.create_pager(column_count);
+        let pager_rows = usize::from(pager.is_some());
+        let (slots, row_count, placed_column_count) = instance::place(
+            letters
+                .into_iter()
+                .chain(header)
+                .chain(body)
+                .chain(footer)
+                .chain(pager),
+            &self.areas,
+            header_rows,
+            self.auto_flow_dense,
+        );
+        let column_count = column_count.max(placed_column_count);
+        let pager_row = Some(header_rows + page.len() + footer_rows).filter(|_| pager_rows > 0);
+        let (row_heights, row_limits) =
+            self.row_tracks(row_count, header_rows, &body_rows, pager_row);
+        let (column_widths, column_limits) = self.column_tracks(column_count, number_columns);
+        let (row_separators, column_separators) =
+            self.create_separators(&slots, row_count, column_count);
+        Instance {
+            slots,
+            column_widths,
+            row_heights,
+            column_limits,
+            row_limits,
+            aspect_ratio: self.aspect_ratio,
+            column_gutter: self.column_gutter.0,
+            row_gutter: self.row_gutter.0,
+            padding: self.padding.0,
+            id: self.id.clone(),
+            justify_content: self.justify_content,
+            align_content: self.align_content,
+            lines: self.lines,
+            row_separators,
+            column_separators,
+            collapse_borders: self.collapse_borders,
+            corner_radius: self.corner_radius,
+            on_press: self.on_press.clone(),
+            on_double_press: self.on_double_press.clone(),
+            double_press_interval: self.double_press_interval,
+            scroll_animation: self.scroll_animation,
+            row_transition: self.row_transition,
+            loading_rows: self.loading_rows(),
+            on_request_rows: self.on_request_rows.clone(),
+            on_row_press: self.on_row_press.clone(),
+            on_row_select: self.on_row_select.clone(),
+            on_column_select: self.on_column_select.clone(),
+            on_right_press: self.on_right_press.clone(),
+            on_paint: self.on_paint.clone(),
+            on_hover: self.on_hover.clone(),
+            on_range_select: self.on_range_select.clone(),
+            on_paste: self.on_paste.clone(),
+            selected: self.selected,
+            on_select: self.on_select.clone(),
+            selection: self.selection.clone(),
+            copying: std::cell::Cell::new(false),
+            on_selection_change: self.on_selection_change.clone(),
+            on_focus_cell: self.on_focus_cell.clone(),
+            tab_traversal: self.tab_traversal,
+            focus_ring: self.focus_ring,
+            header_rows,
+            footer_rows,
+            pager_rows,
+            body_rows,
+            row_keys: self.row_keys.clone(),
+            on_row_reorder: self.on_row_reorder.clone(),
+            on_column_reorder: self.on_column_reorder.clone(),
+            on_column_resize: self.on_column_resize.clone(),
+            window,
+            reflow,
+            viewport_height: self.virtualized.map(|height| height.0),
+            scrollable: self.scrollable,
+            frozen_columns: self.frozen_columns + number_columns,
+            number_columns,
+            frame_style: self.frame_style.clone(),
+            sort: self.sort,
+            sortable: self.sort_keys.iter().map(Option::is_some).collect(),
+            on_sort: self.on_sort.clone(),
+            right_to_left: false,
+        }
+        .mirrored(self.text_direction == Direction::Rtl)
+    }
+
+    /// Create the rows above the body of an instance of the grid with the given number of columns,
+    /// which are the row of letters naming the columns and the header, if the grid has them, each
+    /// starting with the corner above the row numbers if the rows are numbered.
+    fn create_top(
+        &self,
+        column_count: usize,
+        numbered: bool,
+        reflow: Option<usize>,
+    ) -> (OptionalRow<'a, M, T, R>, OptionalRow<'a, M, T, R>) {
+        let corner = || numbered.then(|| Some((Part::Corner, self.styled_header(Cell::empty()))));
+        let letters = self
+            .column_letters
+            .filter(|_| reflow.is_none())
+            .map(|letter| {
+                corner()
+                    .into_iter()
+                    .chain(self.letter(column_count, letter))
+                    .collect()
+            });
+        let header = self
+            .create_header()
+            .map(|header| corner().into_iter().chain(header).collect());
+        (letters, header)
+    }
+
+    /// Count the rows above the body of an instance of the grid reflowed into the given number of
+    /// columns, if any, which are the row of letters naming the columns and the header.
+    fn header_rows(&self, reflow: Option<usize>) -> usize {
+        usize::from(self.column_letters.is_some() && reflow.is_none())
+            + usize::from(self.header.is_some())
+    }
+
+    /// Find the number of columns the cells of the grid take up before they are placed, which is
+    /// the number of columns they are reflowed into if they are reflowed.
+    fn column_count_for(&self, reflow: Option<usize>) -> usize {
+        reflow
+            .into_iter()
+            .chain(self.header.iter().map(Vec::len))
+            .chain(self.rows.iter().filter(|_| reflow.is_none()).map(Vec::len))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Create the cells of the header, if the grid has one.
+    fn create_header(&self) -> Option<Vec<Placement<'a, M, T, R>>> {
+        self.header.as_ref().map(|factories| {
+            factories
+                .iter()
+                .enumerate()
+                .map(|(column, factory)| {
+                    factory.as_ref().map(|factory| {
+                        (
+                            Part::Header(column),
+                            self.styled_header(self.create_outside(factory, column)),
+                        )
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// Create the separators in the gutters between the given number of rows and columns, given
+    /// by the track after each gutter. Gutters between rows without any cells, like the rows
+    /// outside the view of a virtualized grid, are left without a separator.
+    fn create_separators(
+        &self,
+        slots: &[Slot<'a, M, T, R>],
+        row_count: usize,
+        column_count: usize,
+    ) -> (Separators<'a, M, T, R>, Separators<'a, M, T, R>) {
+        let mut filled = vec![false; row_count];
+        for slot in slots {
+            filled[slot.row..slot.row + slot.cell.row_span].fill(true);
+        }
+        let rows = self
+            .row_separator
+            .as_ref()
+            .map_or_else(Vec::new, |separator| {
+                (1..row_count)
+                    .filter(|&row| filled[row - 1] || filled[row])
+                    .map(|row| (row, separator()))
+                    .collect()
+            });
+        let columns = self
+            .column_separator
+            .as_ref()
+            .map_or_else(Vec::new, |separator| {
+                (1..column_count)
+                    .map(|column| (column, separator()))
+                    .collect()
+            });
+        (rows, columns)
+    }
+
+    /// Create the cells of the footer, if the grid has one, with the aggregates of the columns that
+    /// have them in place of their cells. The footer starts with an empty slot below the row
+    /// numbers if the rows are numbered.
+    fn create_footer(&self, numbered: bool) -> OptionalRow<'a, M, T, R> {
+        if self.footer.is_none() && self.aggregates.is_empty() {
+            return None;
+        }
+        let mut cells: Vec<Placement<'a, M, T, R>> = self
+            .footer
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(column, factory)| {
+                factory.as_ref().map(|factory| {
+                    (
+                        Part::Footer(column),
+                        self.styled_header(self.create_outside(factory, column)),
+                    )
+                })
+            })
+            .collect();
+        if let Some(label) = self.aggregate_label {
+            let rows: Vec<usize> = (0..self.rows.len())
+                .filter(|&row| self.is_shown(row))
+                .collect();
+            for (column, aggregate, value, format) in &self.aggregates {
+                let result = aggregate.apply(rows.iter().filter_map(|&row| value(row)));
+                let cell = Cell::from(label(
+                    result.map_or_else(String::new, |result| format.format(result)),
+                ));
+                if cells.len() <= *column {
+                    cells.resize_with(column + 1, || None);
+                }
+                cells[*column] = Some((Part::Footer(*column), self.styled_header(cell)));
+            }
+        }
+        if numbered {
+            cells.insert(0, None);
+        }
+        Some(cells)
+    }
+
+    /// Create the row of controls for changing pages, spanning the given number of columns, if the
+    /// grid is paginated.
+    fn create_pager(&self, column_count: usize) -> Option<Vec<Placement<'a, M, T, R>>> {
+        self.pager_row
+            .filter(|_| self.pager.is_some())
+            .map(|pager_row| {
+                vec![Some((
+                    Part::Pager,
+                    Cell::from(pager_row(self)).span(column_count.max(1)),
+                ))]
+            })
+    }
+
+    /// Create the cells naming each of the given number of columns with letters.
+    fn letter(
+        &self,
+        column_count: usize,
+        letter: Label<'a, M, T, R>,
+    ) -> impl Iterator<Item = Placement<'a, M, T, R>> {
+        (0..column_count).map(move |column| {
+            let cell = self.styled_header(Cell::from(letter(column_name(column))));
+            Some((Part::ColumnLetter(column), cell))
+        })
+    }
+
+    /// Put the number of each row of the body before its cells, given the logical row shown at
+    /// each row of the body. Rows that are left empty are not numbered.
+    fn number(
+        &self,
+        body: &mut [Vec<Placement<'a, M, T, R>>],
+        body_rows: &[usize],
+        number: Label<'a, M, T, R>,
+    ) {
+        for (cells, &row) in body.iter_mut().zip(body_rows) {
+            if !cells.is_empty() {
+                let cell = self.styled_header(Cell::from(number((row + 1).to_string())));
+                cells.insert(0, Some((Part::RowNumber(row), cell)));
+            }
+        }
+    }
+
+    /// Find the width and limits of each column of an instance of the grid, given the number of
+    /// columns of row numbers before the columns of the grid, which are as wide as their numbers.
+    fn column_tracks(
+        &self,
+        column_count: usize,
+        number_columns: usize,
+    ) -> (Vec<Length>, Vec<(f32, f32)>) {
+        (0..column_count)
+            .map(|track| {
+                track
+                    .checked_sub(number_columns)
+                    .map_or((Length::Shrink, UNLIMITED), |column| {
+                        (
+                            *self.column_widths.get(column).unwrap_or(&self.cell_width),
+                            *self.column_limits.get(column).unwrap_or(&UNLIMITED),
+                        )
+                    })
+            })
+            .unzip()
+    }
+
+    /// Find the height and limits of each row of an instance of the grid, given the number of
+    /// header rows above the body, the logical row shown at each row of the body and the row the
+    /// pager is in, if there is one.
+    fn row_tracks(
+        &self,
+        row_count: usize,
+        header_rows: usize,
+        body_rows: &[usize],
+        pager_row: Option<usize>,
+    ) -> (Vec<Length>, Vec<(f32, f32)>) {
+        (0..row_count)
+            .map(|row| {
+                if row < header_rows {
+                    (self.header_height.unwrap_or(self.cell_height), UNLIMITED)
+                } else if pager_row == Some(row) || self.aspect_ratio.is_some() {
+                    (Length::Shrink, UNLIMITED)
+                } else {
+                    let row = body_rows.get(row - header_rows);
+                    (
+                        *row.and_then(|&row| self.row_heights.get(row))
+                            .unwrap_or(&self.cell_height),
+                        *row.and_then(|&row| self.row_limits.get(row))
+                            .unwrap_or(&UNLIMITED),
+                    )
+                }
+            })
+            .unzip()
+    }
+
+    /// Create a new grid from a flat sequence of cells, starting a new row after every `columns`
+    /// cells. The last row is left short if the cells run out before it is filled.
+    ///
+    /// A `columns` of zero is treated as one.
+    #[must_use]
+    pub fn from_iter<C: Into<Factory<'a, M, T, R>>>(
+        items: impl IntoIterator<Item = C>,
+        columns: usize,
+    ) -> Self {
+        let columns = columns.max(1);
+        let mut items = items.into_iter().map(|item| Some(item.into())).peekable();
+        let mut rows = Vec::new();
+        while items.peek().is_some() {
+            rows.push(items.by_ref().take(columns).collect());
+        }
+        Self {
+            rows,
+            ..Self::default()
+        }
+    }
+
+    /// Create a new grid with the given number of rows and columns, where each cell is created by
+    /// calling the given function with its row and column.
+    #[must_use]
+    pub fn from_fn<F: Fn(usize, usize) -> Cell<'a, M, T, R> + 'a>(
+        rows: usize,
+        columns: usize,
+        function: F,
+    ) -> Self {
+        let factory = Factory::from_indexed(function);
+        Self {
+            rows: (0..rows)
+                .map(|_| (0..columns).map(|_| Some(factory.clone())).collect())
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Create a new grid showing the rows and columns of the given model, creating the cells of
+    /// each row from the model only once the row is needed, like the rows added with
+    /// [`Self::with_lazy_rows`].
+    ///
+    /// This keeps the data of the grid in the model, so a [virtualized](Self::virtualized) grid
+    /// only asks the model for the cells that are in view.
+    #[must_use]
+    pub fn from_model(model: &'a impl GridModel<'a, M, T, R>) -> Self {
+        let columns = model.columns();
+        Self::default().with_lazy_rows(model.rows(), move |row| {
+            (0..columns)
+                .map(|column| Factory::from_factory(move || model.cell(row, column)))
+                .collect()
+        })
+    }
+
+    /// Create a new grid from rows of values of any type, creating the cell of each value with the
+    /// renderer the given registry has for its type. Values of types without a renderer are left
+    /// as empty cells.
+    ///
+    /// This saves matching on the type of each column of a table of values of mixed types.
+    #[must_use]
+    pub fn from_values(
+        rows: impl IntoIterator<Item = impl IntoIterator<Item = Box<dyn Any>>>,
+        registry: Registry<'a, M, T, R>,
+    ) -> Self {
+        let registry = Rc::new(registry);
+        let mut grid = Self::default();
+        for row in rows {
+            grid.push_line(row.into_iter().map(|value| {
+                let registry = registry.clone();
+                Factory::from_factory(move || registry.render(&*value).unwrap_or_else(Cell::empty))
+            }));
+        }
+        grid
+    }
+
+    /// Add a row to the grid, or a column if the grid flows in [`FlowDirection::ColumnMajor`] order.
+    #[must_use]
+    pub fn with_row<C: Into<Factory<'a, M, T, R>>>(
+        mut self,
+        row: impl IntoIterator<Item = C>,
+    ) -> Self {
+        self.push_line(row);
+        self
+    }
+
+    /// Add a row to the grid with the given key, which tells the row apart from the other rows of
+    /// the grid from one `view` to the next, like the keys of `iced`'s `keyed_column`.
+    ///
+    /// The state of the cells of a keyed row, like the cursor of a text input or how far a
+    /// scrollable is scrolled, stays with the row when rows are added, removed, or moved around
+    /// it, instead of staying at the index the row was at. The row is added as a row even if the
+    /// grid flows in [`FlowDirection::ColumnMajor`] order.
+    #[must_use]
+    pub fn with_keyed_row<C: Into<Factory<'a, M, T, R>>>(
+        mut self,
+        key: impl Hash,
+        row: impl IntoIterator<Item = C>,
+    ) -> Self {
+        let mut cells = Vec::with_capacity(self.column_capacity);
+        cells.extend(row.into_iter().map(|cell| Some(cell.into())));
+        self.row_keys.resize(self.rows.len(), None);
+        self.rows.push(cells);
+        if !self.lazy_rows.is_empty() {
+            self.lazy_rows.push(None);
+        }
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        self.row_keys.push(Some(hasher.finish()));
+        self
+    }
+
+    /// Set the number of cells in each row of cells added with [`Self::push`], so that once the last
+    /// row has that many cells, the next cell starts a new row.
+    #[must_use]
+    pub const fn columns(mut self, columns: usize) -> Self {
+        self.wrap_columns = Some(columns);
+        self
+    }
+
+    /// Add a cell to the end of the last row of the grid, or to a new row if the grid has no rows
+    /// yet or the last row already has as many cells as set with [`Self::columns`]. This is for
+    /// laying out flat lists of cells in rows without splitting them into rows first.
+    pub fn push(&mut self, factory: impl Into<Factory<'a, M, T, R>>) {
+        self.materialize();
+        let full = self.rows.last().is_none_or(|cells| {
+            self.wrap_columns
+                .is_some_and(|columns| cells.len() >= columns)
+        });
+        if full {
+            let capacity = self.wrap_columns.unwrap_or(self.column_capacity);
+            self.rows.push(Vec::with_capacity(capacity));
+            if !self.lazy_rows.is_empty() {
+                self.lazy_rows.push(None);
+            }
+        }
+        if let Some(cells) = self.rows.last_mut() {
+            cells.push(Some(factory.into()));
+        }
+    }
+
+    /// Add a column to the grid after the end of its longest row, whichever direction the grid
+    /// flows in. The first cell goes into the first row, the second into the second row, and so
+    /// on, adding rows to the grid if there are more cells than rows. Shorter rows are padded with
+    /// empty slots to reach the column. See [`Self::insert_column`] for more information.
+    #[must_use]
+    pub fn with_column<C: Into<Factory<'a, M, T, R>>>(
+        mut self,
+        column: impl IntoIterator<Item = C>,
+    ) -> Self {
+        self.materialize();
+        let index = self.rows.iter().map(Vec::len).max().unwrap_or(0);
+        self.insert_column(index, column);
+        self
+    }
+
+    /// Add multiple rows to the grid, or multiple columns if the grid flows in
+    /// [`FlowDirection::ColumnMajor`] order.
+    #[must_use]
+    pub fn with_rows<B: IntoIterator<Item = C>, C: Into<Factory<'a, M, T, R>>>(
+        mut self,
+        rows: impl IntoIterator<Item = B>,
+    ) -> Self {
+        for row in rows {
+            self.push_line(row);
+        }
+        self
+    }
+
+    /// Add rows to the grid that are only created once they are needed, by calling the given
+    /// function with the index of each row to get the factories of its cells. The index is the
+    /// one the row is added at, even after rows are inserted or removed before it.
+    ///
+    /// Rows are only needed when the grid is turned into a widget, and only the rows in view when
+    /// the grid is [virtualized](Self::virtualized), so the function is never called for rows that
+    /// are never seen. The rows are added as rows even if the grid flows in
+    /// [`FlowDirection::ColumnMajor`] order.
+    ///
+    /// The cells of lazy rows cannot be accessed with [`Self::get`] until they are created, which
+    /// happens to every lazy row when the grid is changed in a way that needs their cells, like
+    /// with [`Self::get_mut`], [`Self::set_cell`], [`Self::transpose`], or inserting and removing
+    /// columns.
+    #[must_use]
+    pub fn with_lazy_rows(
+        mut self,
+        count: usize,
+        factory: impl Fn(usize) -> Vec<Factory<'a, M, T, R>> + 'a,
+    ) -> Self {
+        let factory: RowFactory<'a, M, T, R> = Rc::new(factory);
+        self.lazy_rows.resize_with(self.rows.len(), || None);
+        let start = self.rows.len();
+        self.rows.resize_with(start + count, Vec::new);
+        self.lazy_rows
+            .extend((start..start + count).map(|index| Some((index, Rc::clone(&factory)))));
+        self
+    }
+
+    /// Create the cells of every lazy row, so the cells of every row can be changed.
+    fn materialize(&mut self) {
+        for (row, lazy_row) in std::mem::take(&mut self.lazy_rows).into_iter().enumerate() {
+            if let Some((index, factory)) = lazy_row {
+                self.rows[row] = factory(index).into_iter().map(Some).collect();
+            }
+        }
+    }
+
+    /// Add a line of cells to the grid, as a row or as a column depending on the flow direction.
+    fn push_line<C: Into<Factory<'a, M, T, R>>>(&mut self, line: impl IntoIterator<Item = C>) {
+        let line = line.into_iter().map(|cell| Some(cell.into()));
+        match self.direction {
+            FlowDirection::RowMajor => {
+                let mut cells = Vec::with_capacity(self.column_capacity);
+                cells.extend(line);
+                self.rows.push(cells);
+                if !self.lazy_rows.is_empty() {
+                    self.lazy_rows.push(None);
+                }
+            }
+            FlowDirection::ColumnMajor => {
+                self.materialize();
+                let column = self.rows.iter().map(Vec::len).max().unwrap_or_default();
+                for (row, cell) in line.enumerate() {
+                    if self.rows.len() <= row {
+                        self.rows.push(Vec::with_capacity(self.column_capacity));
+                    }
+                    self.rows[row].resize_with(column, || None);
+                    self.rows[row].push(cell);
+                }
+            }
+        }
+    }
+
+    /// Place a cell at the given row and column, replacing any cell already there. If the grid does
+    /// not reach that far yet, it is grown with empty slots until it does.
+    ///
+    /// Empty slots take up space in the grid like any other cell, but nothing is drawn in them.
+    #[must_use]
+    pub fn with_cell_at(
+        mut self,
+        row: usize,
+        column: usize,
+        factory: impl Into<Factory<'a, M, T, R>>,
+    ) -> Self {
+        self.set_cell(row, column, factory);
+        self
+    }
+
+    /// Place a cell at the given row and column, replacing any cell already there. See
+    /// [`Self::with_cell_at`] for more information.
+    pub fn set_cell(
+        &mut self,
+        row: usize,
+        column: usize,
+        factory: impl Into<Factory<'a, M, T, R>>,
+    ) {
+        self.materialize();
+        if self.rows.len() <= row {
+            self.rows.resize_with(row + 1, Vec::new);
+        }
+        let cells = &mut self.rows[row];
+        if cells.len() <= column {
+            cells.resize_with(column + 1, || None);
+        }
+        cells[column] = Some(factory.into());
+    }
+
+    /// Swap the rows and columns of the grid, so that the cell at row `r` and column `c` ends up at
+    /// row `c` and column `r`. Rows that are shorter than others leave empty slots behind.
+    ///
+    /// The sizing of rows and columns is swapped along with them, so [`Self::cell_width`] becomes
+    /// the cell height and [`Self::column_widths`] become the row heights, and so on for gutters.
+    /// Spans are part of each cell, so they are not swapped, but blocks merged with
+    /// [`Self::merge`] are, and the keys of rows added with [`Self::with_keyed_row`] are dropped.
+    #[must_use]
+    pub fn transpose(mut self) -> Self {
+        self.materialize();
+        Self {
+            rows: transposed(self.rows),
+            row_keys: Vec::new(),
+            cell_width: self.cell_height,
+            cell_height: self.cell_width,
+            column_widths: self.row_heights,
+            row_heights: self.column_widths,
+            column_limits: self.row_limits,
+            row_limits: self.column_limits,
+            areas: self
+                .areas
+                .into_iter()
+                .map(|area| Area {
+                    rows: area.columns,
+                    columns: area.rows,
+                    ..area
+                })
+                .collect(),
+            merges: transposed_merges(self.merges),
+            column_gutter: self.row_gutter,
+            row_gutter: self.column_gutter,
+            instance: RefCell::new(None),
+            ..self
+        }
+    }
+
+    /// Set the direction in which cells flow when lines of cells are added with [`Self::with_row`]
+    /// and [`Self::with_rows`]. With [`FlowDirection::ColumnMajor`], each line is added as a new
+    /// column on the right of the grid, so cells flow from top to bottom and then from left to
+    /// right.
+    ///
+    /// Cells already in the grid are reflowed, so a grid made with [`Self::from_iter`] is filled
+    /// column by column with `columns` cells in each column instead. Blocks merged with
+    /// [`Self::merge`] are swapped along with the cells they cover, and the keys of rows added
+    /// with [`Self::with_keyed_row`] are dropped.
+    ///
+    /// Everywhere else, rows and columns still refer to the rows and columns seen on screen.
+    #[must_use]
+    pub fn direction(mut self, direction: FlowDirection) -> Self {
+        if direction != self.direction {
+            self.materialize();
+            self.rows = transposed(self.rows);
+            self.merges = transposed_merges(self.merges);
+            self.row_keys.clear();
+            self.direction = direction;
+        }
+        self
+    }
+
+    /// Set whether cells fill the holes left behind by cells spanning several rows or columns,
+    /// like `grid-auto-flow: dense` in CSS. Each cell of the body is moved up into the first hole
+    /// it fits in, searching the rows above it from left to right and only as far right as the
+    /// grid already reaches, so cells may end up out of order.
+    ///
+    /// Rows left empty by cells moving up keep their size.
+    #[must_use]
+    pub const fn auto_flow_dense(mut self, dense: bool) -> Self {
+        self.auto_flow_dense = dense;
+        self
+    }
+
+    /// Set the direction in which the columns of the grid are laid out. With [`Direction::Rtl`],
+    /// the first column is on the right and the horizontal alignment of each cell is mirrored, so
+    /// a cell aligned to the left is aligned to the right instead. The arrow keys move between
+    /// cells in the direction they point on screen.
+    ///
+    /// Columns keep their indices, so messages and sizes given for columns still count from the
+    /// first column. Frozen columns are still the leftmost columns on screen.
+    #[must_use]
+    pub const fn text_direction(mut self, direction: Direction) -> Self {
+        self.text_direction = direction;
+        self
+    }
+
+    /// Set what the grid does with rows that have fewer or more cells than other rows. By
+    /// default, rows are laid out as they are.
+    ///
+    /// # Panics
+    ///
+    /// With [`RaggedRows::Error`], the grid panics when it is turned into a widget if its rows
+    /// are not as long as each other.
+    #[must_use]
+    pub const fn ragged_rows(mut self, ragged_rows: RaggedRows) -> Self {
+        self.ragged_rows = ragged_rows;
+        self
+    }
+
+    /// Get the factory of the cell at the given row and column, if there is one. Cells in lazy rows
+    /// added with [`Self::with_lazy_rows`] are not found until they are created.
+    #[must_use]
+    pub fn get(&self, row: usize, column: usize) -> Option<&Factory<'a, M, T, R>> {
+        self.rows.get(row)?.get(column)?.as_ref()
+    }
+
+    /// Get a mutable reference to the factory of the cell at the given row and column, if there is
+    /// one. Lazy rows are created first, so their cells can be found.
+    #[must_use]
+    pub fn get_mut(&mut self, row: usize, column: usize) -> Option<&mut Factory<'a, M, T, R>> {
+        self.materialize();
+        self.rows.get_mut(row)?.get_mut(column)?.as_mut()
+    }
+
+    /// Get the number of rows in the grid, not counting the header.
+    #[must_use]
+    pub const fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Get the number of columns in the grid, which is the number of cells in its longest row or in
+    /// its header, counting empty slots. Lazy rows added with [`Self::with_lazy_rows`] are not
+    /// counted until they are created.
+    #[must_use]
+    pub fn column_count(&self) -> usize {
+        self.header
+            .iter()
+            .chain(&self.rows)
+            .map(Vec::len)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Check whether the grid has no rows.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Iterate over the rows of the grid, not counting the header, with [`None`] in place of
+    /// empty slots. Lazy rows added with [`Self::with_lazy_rows`] are empty until they are
+    /// created.
+    pub fn rows(&self) -> impl Iterator<Item = &[Option<Factory<'a, M, T, R>>]> {
+        self.rows.iter().map(Vec::as_slice)
+    }
+
+    /// Iterate over the cells of the grid with their rows and columns, row by row, leaving out the
+    /// header and empty slots. Cells in lazy rows added with [`Self::with_lazy_rows`] are not found
+    /// until they are created.
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize, &Factory<'a, M, T, R>)> {
+        self.rows.iter().enumerate().flat_map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .filter_map(move |(column, cell)| Some((row, column, cell.as_ref()?)))
+        })
+    }
+
+    /// Insert a row into the grid at the given index, shifting the rows after it down.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than the number of rows in the grid.
+    pub fn insert_row<C: Into<Factory<'a, M, T, R>>>(
+        &mut self,
+        index: usize,
+        row: impl IntoIterator<Item = C>,
+    ) {
+        self.rows.insert(
+            index,
+            row.into_iter().map(|cell| Some(cell.into())).collect(),
+        );
+        if !self.lazy_rows.is_empty() {
+            self.lazy_rows.insert(index, None);
+        }
+        if index < self.row_keys.len() {
+            self.row_keys.insert(index, None);
+        }
+        self.move_versions(inserted(index), Some);
+    }
+
+    /// Remove the row at the given index from the grid, shifting the rows after it up. The cells
+    /// of the removed row are returned, with [`None`] in place of empty slots.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn remove_row(&mut self, index: usize) -> Vec<Option<Factory<'a, M, T, R>>> {
+        let cells = self.rows.remove(index);
+        if index < self.row_keys.len() {
+            self.row_keys.remove(index);
+        }
+        self.move_versions(removed(index), Some);
+        if self.lazy_rows.is_empty() {
+            return cells;
+        }
+        self.lazy_rows
+            .remove(index)
+            .map_or(cells, |(index, factory)| {
+                factory(index).into_iter().map(Some).collect()
+            })
+    }
+
+    /// Insert a column into the grid at the given index, shifting the cells after it in each row
+    /// to the right. The first cell goes into the first row, the second into the second row, and
+    /// so on, adding rows to the grid if there are more cells than rows.
+    ///
+    /// Rows that are too short to reach the column are grown with empty slots, and rows that get
+    /// no cell from the column get an empty slot instead if they have cells after it.
+    pub fn insert_column<C: Into<Factory<'a, M, T, R>>>(
+        &mut self,
+        index: usize,
+        column: impl IntoIterator<Item = C>,
+    ) {
+        self.materialize();
+        self.move_versions(Some, inserted(index));
+        let mut column = column.into_iter().map(Into::into);
+        let mut row = 0;
+        loop {
+            let cell = column.next();
+            if cell.is_none() && row >= self.rows.len() {
+                break;
+            }
+            if self.rows.len() <= row {
+                self.rows.push(Vec::new());
+            }
+            let cells = &mut self.rows[row];
+            if cell.is_some() || cells.len() > index {
+                if cells.len() < index {
+                    cells.resize_with(index, || None);
+                }
+                cells.insert(index, cell);
+            }
+            row += 1;
+        }
+    }
+
+    /// Remove the column at the given index from each row in the grid, shifting the cells after it
+    /// to the left. The cells of the removed column are returned, with [`None`] in place of empty
+    /// slots and rows too short to reach the column.
+    pub fn remove_column(&mut self, index: usize) -> Vec<Option<Factory<'a, M, T, R>>> {
+        self.materialize();
+        self.move_versions(Some, removed(index));
+        self.rows
+            .iter_mut()
+            .map(|cells| {
+                if cells.len() > index {
+                    cells.remove(index)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Move the versions given to rows and cells by [`Self::invalidate_row`] and
+    /// [`Self::invalidate`] along with the rows and columns they belong to, to where the given
+    /// functions move them, dropping the versions of rows and columns that are removed.
+    fn move_versions(
+        &mut self,
+        row: impl Fn(usize) -> Option<usize>,
+        column: impl Fn(usize) -> Option<usize>,
+    ) {
+        self.row_versions = std::mem::take(&mut self.row_versions)
+            .into_iter()
+            .filter_map(|(index, version)| Some((row(index)?, version)))
+            .collect();
+        self.cell_versions = std::mem::take(&mut self.cell_versions)
+            .into_iter()
+            .filter_map(|((index, other), version)| Some(((row(index)?, column(other)?), version)))
+            .collect();
+    }
+
+    /// Invalidate the cell at the given row and column, so that if it is cached, it is created again
+    /// the next time the grid is turned into a widget. See `Factory::cached`.
+    pub fn invalidate(&mut self, row: usize, column: usize) {
+        *self.cell_versions.entry((row, column)).or_default() += 1;
+    }
+
+    /// Invalidate every cell of the row at the given index, so that cached cells in it are created
+    /// again the next time the grid is turned into a widget. See `Factory::cached`.
+    pub fn invalidate_row(&mut self, row: usize) {
+        *self.row_versions.entry(row).or_default() += 1;
+    }
+
+    /// Invalidate every cell of the grid, so that cached cells are created again the next time the
+    /// grid is turned into a widget. See `Factory::cached`.
+    pub const fn invalidate_all(&mut self) {
+        self.version += 1;
+    }
+
+    /// Set the header of the grid, a row of cells shown above all other rows. The header is not one
+    /// of the rows of the grid, so the first row after it is still the row at index zero, and it is
+    /// left out of row styling like [`Self::striped`].
+    ///
+    /// Factories of header cells are given a row of zero, like the cells of the first row, so a
+    /// factory that uses the row, like one shared with the rows, creates the same cells in the
+    /// header as in the first row. Cached header cells are only created again by
+    /// [`Self::invalidate_all`], not by invalidating the first row or its cells.
+    #[must_use]
+    pub fn header<C: Into<Factory<'a, M, T, R>>>(
+        mut self,
+        header: impl IntoIterator<Item = C>,
+    ) -> Self {
+        self.header = Some(header.into_iter().map(|cell| Some(cell.into())).collect());
+        self
+    }
+
+    /// Set the footer of the grid, a row of cells shown below all other rows, above the controls
+    /// for changing pages of a paginated grid. Like the header, the footer is not one of the rows
+    /// of the grid and is styled like the header. Factories of footer cells are given a row of
+    /// zero, like the factories of header cells; see [`Self::header`].
+    ///
+    /// The footer stays in place at the bottom of a grid that scrolls, along with the controls for
+    /// changing pages, while the rows scroll above it.
+    #[must_use]
+    pub fn footer<C: Into<Factory<'a, M, T, R>>>(
+        mut self,
+        footer: impl IntoIterator<Item = C>,
+    ) -> Self {
+        self.footer = Some(footer.into_iter().map(|cell| Some(cell.into())).collect());
+        self
+    }
+
+    /// Set the style of the cells in the header, to set it apart from the rest of the grid. The
+    /// style of each header cell is layered on top of this style, so parts of the style of a cell
+    /// that are left unset, like the background, are taken from the header.
+    #[must_use]
+    pub fn header_style(mut self, header_style: impl Into<Style>) -> Self {
+        self.header_style = Some(header_style.into());
+        self
+    }
+
+    /// Set the style of the empty cells in the grid, made with [`Cell::empty`], in place of their
+    /// own style. Rows padded with [`RaggedRows::PadWithEmpty`] are padded with empty cells.
+    #[must_use]
+    pub fn placeholder_style(mut self, placeholder_style: impl Into<Style>) -> Self {
+        self.placeholder_style = Some(placeholder_style.into());
+        self
+    }
+
+    /// Set the height of the header, which is the height set with [`Self::cell_height`] by default.
+    #[must_use]
+    pub fn header_height(mut self, header_height: impl Into<Length>) -> Self {
+        self.header_height = Some(header_height.into());
+        self
+    }
+
+    /// Set the width of each cell in the grid.
+    ///
+    /// Like other `iced` widgets, the width can be any [`Length`]. A column with a width of
+    /// [`Length::Shrink`] is as wide as its widest cell, and columns with a width of
+    /// [`Length::Fill`] or [`Length::FillPortion`] share the space left over by the other columns.
+    #[must_use]
+    pub fn cell_width(mut self, cell_width: impl Into<Length>) -> Self {
+        self.cell_width = cell_width.into();
+        self
+    }
+
+    /// Set the height of each cell in the grid.
+    ///
+    /// Like other `iced` widgets, the height can be any [`Length`]. A row with a height of
+    /// [`Length::Shrink`] is as tall as its tallest cell, and rows with a height of
+    /// [`Length::Fill`] or [`Length::FillPortion`] share the space left over by the other rows.
+    #[must_use]
+    pub fn cell_height(mut self, cell_height: impl Into<Length>) -> Self {
+        self.cell_height = cell_height.into();
+        self
+    }
+
+    /// Size every column and row of the grid to its content, so each column is as wide as its
+    /// widest cell and each row is as tall as its tallest cell. This is the same as setting both
+    /// the cell width and the cell height to [`Length::Shrink`].
+    ///
+    /// Cells spanning several columns or rows widen the columns or heighten the rows they span
+    /// evenly if they do not fit in them.
+    #[must_use]
+    pub fn auto_size(self) -> Self {
+        self.cell_width(Length::Shrink).cell_height(Length::Shrink)
+    }
+
+    /// Make each row of the grid as tall as the widest column divided by the given ratio of width
+    /// to height, so a ratio of one makes square cells. The rows follow the widths the columns
+    /// are laid out with, so cells keep their shape as columns filling the grid grow and shrink.
+    ///
+    /// The ratio replaces the cell height and row heights of the rows below the header. A ratio
+    /// that is not a positive number is ignored.
+    #[must_use]
+    pub fn cell_aspect_ratio(mut self, ratio: f32) -> Self {
+        self.aspect_ratio = Some(ratio).filter(|ratio| ratio.is_finite() && *ratio > 0.0);
+        self
+    }
+
+    /// Set the width of each column in the grid, starting from the first column. Columns without a
+    /// width given here fall back to the width set with [`Self::cell_width`].
+    #[must_use]
+    pub fn column_widths(
+        mut self,
+        column_widths: impl IntoIterator<Item = impl Into<Length>>,
+    ) -> Self {
+        self.column_widths = column_widths.into_iter().map(Into::into).collect();
+        self.column_limits.clear();
+        self
+    }
+
+    /// Set the height of each row in the grid, starting from the first row. Rows without a height
+    /// given here fall back to the height set with [`Self::cell_height`].
+    #[must_use]
+    pub fn row_heights(mut self, row_heights: impl IntoIterator<Item = impl Into<Length>>) -> Self {
+        self.row_heights = row_heights.into_iter().map(Into::into).collect();
+        self.row_limits.clear();
+        self
+    }
+
+    /// Set the columns of the grid from a template of tracks, like `grid-template-columns` in CSS.
+    /// Columns past the end of the template fall back to the width set with [`Self::cell_width`].
+    ///
+    /// A [`Track::Fr`] of one takes a hundred times the share of the left over space that a
+    /// column with a width of [`Length::Fill`] does, so templates with fractional tracks are best
+    /// given for every column.
+    #[must_use]
+    pub fn columns_template(self, template: impl IntoIterator<Item = Track>) -> Self {
+        let template: Vec<_> = template.into_iter().collect();
+        let mut grid = self.column_widths(template.iter().copied());
+        grid.column_limits = template.into_iter().map(Track::limits).collect();
+        grid
+    }
+
+    /// Set the rows of the grid from a template of tracks, like `grid-template-rows` in CSS. Rows
+    /// past the end of the template fall back to the height set with [`Self::cell_height`].
+    ///
+    /// A [`Track::Fr`] of one takes a hundred times the share of the left over space that a row
+    /// with a height of [`Length::Fill`] does, so templates with fractional tracks are best given
+    /// for every row.
+    #[must_use]
+    pub fn rows_template(self, template: impl IntoIterator<Item = Track>) -> Self {
+        let template: Vec<_> = template.into_iter().collect();
+        let mut grid = self.row_heights(template.iter().copied());
+        grid.row_limits = template.into_iter().map(Track::limits).collect();
+        grid
+    }
+
+    /// Name a block of rows and columns of the grid, like `grid-template-areas` in CSS, so that
+    /// cells can be placed in it with [`Cell::in_area`]. The rows are counted from the first row
+    /// below the header. Naming an area again replaces it.
+    #[must_use]
+    pub fn area(
+        mut self,
+        name: impl Into<String>,
+        rows: Range<usize>,
+        columns: Range<usize>,
+    ) -> Self {
+        let name = name.into();
+        self.areas.retain(|area| area.name != name);
+        self.areas.push(Area {
+            name,
+            rows,
+            columns,
+        });
+        self
+    }
+
+    /// Merge a block of rows and columns of the body into a single cell, which is created by the
+    /// factory of the cell at the top-left corner of the block and spans the whole block. The
+    /// factories of the other cells of the block are not called, and they do not take up slots of
+    /// their own. The rows are counted from the first row below the header.
+    ///
+    /// Unlike [`Cell::span`] and [`Cell::row_span`], which shift the cells around a spanning cell,
+    /// the cells a merged block covers are left out, so the other cells stay in their columns.
+    /// Merging a block that overlaps blocks merged before replaces them, and empty blocks are
+    /// ignored.
+    #[must_use]
+    pub fn merge(mut self, rows: Range<usize>, columns: Range<usize>) -> Self {
+        if rows.is_empty() || columns.is_empty() {
+            return self;
+        }
+        let merge = Merge { rows, columns };
+        self.merges.retain(|other| !other.overlaps(&merge));
+        self.merges.push(merge);
+        self
+    }
+
+    /// Set how long scrolling the grid to a cell with [`scroll_to`] takes, so it scrolls smoothly
+    /// instead of jumping to the cell. Scrolling with the mouse wheel stops the grid from
+    /// scrolling to the cell.
+    #[must_use]
+    pub const fn scroll_animation(mut self, duration: Duration) -> Self {
+        self.scroll_animation = Some(duration);
+        self
+    }
+
+    /// Animate the rows of the grid for the given time when rows are added, removed, or moved, so
+    /// that a grid updated while it is shown does not jump around. See [`RowTransition`] for how
+    /// the rows are animated.
+    ///
+    /// Rows added with [`Self::with_keyed_row`] are told apart by their keys, and other rows by
+    /// their index, so only unkeyed rows added at the end of the grid are animated as rows being
+    /// added. Rows in the header are never animated.
+    #[must_use]
+    pub const fn row_transition(mut self, transition: RowTransition, duration: Duration) -> Self {
+        self.row_transition = Some((transition, duration));
+        self
+    }
+
+    /// Set the identifier of the grid, so that operations like [`scroll_to`] can find it.
+    #[must_use]
+    pub fn id(mut self, id: widget::Id) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Set how the columns are spread across the width of the grid when they do not fill it, which
+    /// is by packing them together at the start by default. A grid with its columns spread in any
+    /// other way takes up all the width available to it, like a column with a width of
+    /// [`Length::Fill`] makes it do. The start is on the right if the grid is laid out from right
+    /// to left.
+    #[must_use]
+    pub const fn justify_content(mut self, justify_content: Distribution) -> Self {
+        self.justify_content = justify_content;
+        self
+    }
+
+    /// Set how the rows are spread across the height of the grid when they do not fill it, which
+    /// is by packing them together at the top by default. A grid with its rows spread in any other
+    /// way takes up all the height available to it, unless it is virtualized.
+    #[must_use]
+    pub const fn align_content(mut self, align_content: Distribution) -> Self {
+        self.align_content = align_content;
+        self
+    }
+
+    /// Set the size of the gutter between rows and columns in the grid.
+    #[must_use]
+    pub fn gutter(self, gutter: impl Into<Pixels>) -> Self {
+        let gutter = gutter.into();
+        self.column_gutter(gutter).row_gutter(gutter)
+    }
+
+    /// Set the size of the gutter between columns in the grid.
+    #[must_use]
+    pub fn column_gutter(mut self, column_gutter: impl Into<Pixels>) -> Self {
+        self.column_gutter = column_gutter.into();
+        self
+    }
+
+    /// Set the size of the gutter between rows in the grid.
+    #[must_use]
+    pub fn row_gutter(mut self, row_gutter: impl Into<Pixels>) -> Self {
+        self.row_gutter = row_gutter.into();
+        self
+    }
+
+    /// Stripe the rows of the grid with alternating styles, starting with `even` for the first row.
+    /// The style of each cell is layered on top of the style of its row, so parts of the style of a
+    /// cell that are left unset, like the background, are taken from the stripe.
+    #[must_use]
+    pub fn striped(mut self, even: impl Into<Style>, odd: impl Into<Style>) -> Self {
+        self.stripes = Some([even.into(), odd.into()]);
+        self
+    }
+
+    /// Color the cells of the grid like a checkerboard with alternating styles, starting with
+    /// `even` for the first cell and alternating along both rows and columns. The style of each
+    /// cell is layered on top of the style of its square, so parts of the style of a cell that are
+    /// left unset, like the background, are taken from the square. The squares are layered on top
+    /// of stripes set with [`Self::striped`].
+    #[must_use]
+    pub fn checkerboard(mut self, even: impl Into<Style>, odd: impl Into<Style>) -> Self {
+        self.checkerboard = Some([even.into(), odd.into()]);
+        self
+    }
+
+    /// Set the function giving the style of each row, given the index of the row, for styling
+    /// whole rows by what they hold, like coloring rows with errors red or dimming finished rows.
+    /// The style of each cell is layered on top of the style of its row, and the style of the row
+    /// is layered on top of the style of its column set with [`Self::column_style`].
+    #[must_use]
+    pub fn row_style(mut self, row_style: impl Fn(usize) -> Style + 'a) -> Self {
+        self.row_style = Some(Rc::new(row_style));
+        self
+    }
+
+    /// Set the function giving the style of each column, given the index of the column, like a
+    /// distinct background for a column of totals. The header is not styled with it.
+    ///
+    /// Styles are layered from the bottom up in this order, so parts of a style that are left
+    /// unset are taken from the styles beneath it:
+    ///
+    /// 1. the stripe of the row set with [`Self::striped`],
+    /// 2. the square of the cell set with [`Self::checkerboard`],
+    /// 3. the style of the column,
+    /// 4. the style of the row set with [`Self::row_style`],
+    /// 5. the style of the cell given by the function set with [`Self::style_fn`],
+    /// 6. the style of the cell itself,
+    /// 7. the styles of the rules added with [`Self::rule`] that match the cell, in order,
+    /// 8. the style of the selection set with [`Self::selection_style`], if the cell is selected.
+    #[must_use]
+    pub fn column_style(mut self, column_style: impl Fn(usize) -> Style + 'a) -> Self {
+        self.column_style = Some(Rc::new(column_style));
+        self
+    }
+
+    /// Set the function giving the style of each cell of the rows of the grid, given the row and
+    /// column of the cell and the theme, for conditional formatting of the whole grid in one
+    /// place rather than in each factory. The style of each cell is layered on top of the style
+    /// the function gives it. See [`Self::column_style`] for how it is layered with other styles.
+    #[must_use]
+    pub fn style_fn(mut self, style_fn: impl Fn(usize, usize, &T) -> Style + 'a) -> Self {
+        self.style_fn = Some(Rc::new(style_fn));
+        self
+    }
+
+    /// Add a rule of conditional formatting to the grid, styling the cells of the rows of the grid
+    /// that match it. Rules are applied in the order they are added, on top of the style of each
+    /// cell. See [`formatting::Rule`] for more information.
+    #[must_use]
+    pub fn rule(mut self, rule: formatting::Rule<'a>) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Add multiple rules of conditional formatting to the grid, applied in order after the rules
+    /// already added. See [`Self::rule`].
+    #[must_use]
+    pub fn rules(mut self, rules: impl IntoIterator<Item = formatting::Rule<'a>>) -> Self {
+        self.rules.extend(rules);
+        self
+    }
+
+    /// Draw lines of the given width and color between the rows and columns of the grid, centered in
+    /// the gutters. Lines are not drawn through cells that span multiple rows or columns, or around
+    /// the outside of the grid.
+    ///
+    /// Lines wider than the gutters overlap the edges of the cells next to them.
+    #[must_use]
+    pub fn lines(mut self, width: impl Into<Pixels>, color: impl Into<Color>) -> Self {
+        self.lines = Some((width.into().0, color.into()));
+        self
+    }
+
+    /// Set the function creating the element drawn in each gutter between two rows, like a
+    /// horizontal rule, instead of leaving the gutters empty.
+    ///
+    /// A separator is as wide as the columns of the grid and as tall as the gutter it is in,
+    /// and is centered in the gutter, so the gutter between rows should be at least as tall as the
+    /// separator. Separators are drawn beneath the cells, so cells spanning several rows cover
+    /// them, and they do not receive any events.
+    #[must_use]
+    pub fn row_separator<E: Into<Element<'a, M, T, R>>>(
+        mut self,
+        separator: impl Fn() -> E + 'a,
+    ) -> Self {
+        self.row_separator = Some(Rc::new(move || separator().into()));
+        self
+    }
+
+    /// Set the function creating the element drawn in each gutter between two columns, like a
+    /// vertical rule, instead of leaving the gutters empty. See [`Self::row_separator`] for how
+    /// separators are laid out.
+    #[must_use]
+    pub fn column_separator<E: Into<Element<'a, M, T, R>>>(
+        mut self,
+        separator: impl Fn() -> E + 'a,
+    ) -> Self {
+        self.column_separator = Some(Rc::new(move || separator().into()));
+        self
+    }
+
+    /// Set whether the borders of cells next to each other are collapsed into one, like the
+    /// borders of a table, so that a border of one pixel between two cells is drawn one pixel wide
+    /// rather than two. Each cell after the first row and column is drawn overlapping the border
+    /// of the cell above it and the cell before it, so the grid should have no gutter.
+    #[must_use]
+    pub const fn collapse_borders(mut self, collapse_borders: bool) -> Self {
+        self.collapse_borders = collapse_borders;
+        self
+    }
+
+    /// Round the four outer corners of the grid with the given radius: the outer corners of the
+    /// cells in the corners of the grid, and the corners of the frame drawn behind the grid if it
+    /// has one. The other corners of the cells are left square.
+    #[must_use]
+    pub fn corner_radius(mut self, radius: impl Into<Pixels>) -> Self {
+        self.corner_radius = radius.into().0;
+        self
+    }
+
+    /// Set the message to produce when a cell of the grid is pressed, given the row and column of
+    /// the cell. Cells in the header are left out, and presses captured by the element inside a
+    /// cell, like a button, do not produce the message. Pressing Enter while the grid is focused
+    /// presses the focused cell.
+    #[must_use]
+    pub fn on_press(mut self, on_press: impl Fn(usize, usize) -> M + 'a) -> Self {
+        self.on_press = Some(Rc::new(on_press));
+        self
+    }
+
+    /// Set the message to produce when a cell of the grid is pressed twice in a row, given the row
+    /// and column of the cell. The second press only counts if it comes within the interval set
+    /// with [`Self::double_press_interval`] of the first. Both presses still produce the message
+    /// set with [`Self::on_press`], so a cell can be selected by pressing it and opened by
+    /// pressing it twice.
+    #[must_use]
+    pub fn on_double_press(mut self, on_double_press: impl Fn(usize, usize) -> M + 'a) -> Self {
+        self.on_double_press = Some(Rc::new(on_double_press));
+        self
+    }
+
+    /// Set the longest time between two presses of the same cell for them to be a double press,
+    /// which is 300 milliseconds by default.
+    #[must_use]
+    pub const fn double_press_interval(mut self, interval: Duration) -> Self {
+        self.double_press_interval = interval;
+        self
+    }
+
+    /// Set the message to produce when a row of the grid is pressed anywhere, including in the
+    /// gutters between its cells, given the index of the row. This is for lists where whole rows
+    /// are selected rather than single cells. Pressing a cell still produces the message set with
+    /// [`Self::on_press`] as well.
+    #[must_use]
+    pub fn on_row_press(mut self, on_row_press: impl Fn(usize) -> M + 'a) -> Self {
+        self.on_row_press = Some(Rc::new(on_row_press));
+        self
+    }
+
+    /// Set the message to produce when a cell of the grid is pressed with the right mouse button,
+    /// given the row and column of the cell, so the application can open a context menu for it.
+    /// Like with [`Self::on_press`], cells in the header are left out.
+    #[must_use]
+    pub fn on_right_press(mut self, on_right_press: impl Fn(usize, usize) -> M + 'a) -> Self {
+        self.on_right_press = Some(Rc::new(on_right_press));
+        self
+    }
+
+    /// Set the message to produce for each cell painted by the user, given the row and column of
+    /// the cell, like in a pixel art or map editor. Pressing a cell paints it, and moving the
+    /// cursor while the left mouse button is held paints every cell it passes over, even ones it
+    /// skips over between two movements when moved quickly. Each cell is painted once each time
+    /// the cursor enters it.
+    ///
+    /// Rows cannot be reordered and blocks of cells cannot be selected by dragging while cells can
+    /// be painted.
+    #[must_use]
+    pub fn on_paint(mut self, on_paint: impl Fn(usize, usize) -> M + 'a) -> Self {
+        self.on_paint = Some(Rc::new(on_paint));
+        self
+    }
+
+    /// Set the message to produce when the cell under the cursor changes, given the row and column
+    /// of the cell under the cursor, or nothing once the cursor leaves the cells of the grid. Only
+    /// cells of the rows of the grid are hovered, not the header.
+    #[must_use]
+    pub fn on_hover(mut self, on_hover: impl Fn(Option<(usize, usize)>) -> M + 'a) -> Self {
+        self.on_hover = Some(Rc::new(on_hover));
+        self
+    }
+
+    /// Set the message to produce when the user selects a block of cells by dragging from one cell
+    /// to another, given the range of rows and the range of columns of the block. A translucent
+    /// rectangle is drawn over the block while it is being dragged out.
+    ///
+    /// Rows cannot be selected by dragging while they can be reordered with
+    /// [`Self::on_row_reorder`].
+    #[must_use]
+    pub fn on_range_select(
+        mut self,
+        on_range_select: impl Fn(RangeInclusive<usize>, RangeInclusive<usize>) -> M + 'a,
+    ) -> Self {
+        self.on_range_select = Some(Rc::new(on_range_select));
+        self
+    }
+
+    /// Set the selected cell of the grid, if any. The selected cell is drawn with the style set with
+    /// [`Self::selection_style`], and is the anchor that ranges of cells are selected from.
+    #[must_use]
+    pub const fn selected(mut self, selected: Option<(usize, usize)>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Set the cells that are selected along with the cell set with [`Self::selected`], given as
+    /// rows and columns. The selected cells are drawn with the style set with
+    /// [`Self::selection_style`].
+    #[must_use]
+    pub fn selection(mut self, selection: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        self.selection = selection.into_iter().collect();
+        self
+    }
+
+    /// Set the rows that are selected as a whole, so that every cell in them is drawn with the
+    /// style set with [`Self::selection_style`].
+    #[must_use]
+    pub fn selected_rows(mut self, rows: impl IntoIterator<Item = usize>) -> Self {
+        self.selected_rows = rows.into_iter().collect();
+        self
+    }
+
+    /// Set the columns that are selected as a whole, so that every cell in them is drawn with the
+    /// style set with [`Self::selection_style`].
+    #[must_use]
+    pub fn selected_columns(mut self, columns: impl IntoIterator<Item = usize>) -> Self {
+        self.selected_columns = columns.into_iter().collect();
+        self
+    }
+
+    /// Set the message to produce when the number of a row is pressed, given the index of the
+    /// row, so the application can select the whole row with [`Self::selected_rows`]. Rows are
+    /// numbered with [`Self::row_numbers`].
+    #[must_use]
+    pub fn on_row_select(mut self, on_row_select: impl Fn(usize) -> M + 'a) -> Self {
+        self.on_row_select = Some(Rc::new(on_row_select));
+        self
+    }
+
+    /// Set the message to produce when the letter or the header of a column is pressed, given the
+    /// index of the column, so the application can select the whole column with
+    /// [`Self::selected_columns`]. Columns are named with letters with [`Self::column_letters`].
+    ///
+    /// Pressing the header of a column does not select it if pressing it sorts the rows or
+    /// starts dragging the column.
+    #[must_use]
+    pub fn on_column_select(mut self, on_column_select: impl Fn(usize) -> M + 'a) -> Self {
+        self.on_column_select = Some(Rc::new(on_column_select));
+        self
+    }
+
+    /// Set the style of the selected cells. The style is layered on top of the usual style of each
+    /// cell, so parts of it that are left unset, like the text color, stay the same as usual.
+    #[must_use]
+    pub fn selection_style(mut self, selection_style: impl Into<Style>) -> Self {
+        self.selection_style = Some(selection_style.into());
+        self
+    }
+
+    /// Set the message to produce when a cell other than the selected cell is pressed, given the
+    /// row and column of the cell, so the application can select it. Cells in the header cannot be
+    /// selected.
+    #[must_use]
+    pub fn on_select(mut self, on_select: impl Fn(usize, usize) -> M + 'a) -> Self {
+        self.on_select = Some(Rc::new(on_select));
+        self
+    }
+
+    /// Set the message to produce when the user changes the selection by pressing cells, given the
+    /// new selection. See [`SelectionChanged`] for how pressing cells changes the selection.
+    #[must_use]
+    pub fn on_selection_change(
+        mut self,
+        on_selection_change: impl Fn(SelectionChanged) -> M + 'a,
+    ) -> Self {
+        self.on_selection_change = Some(Rc::new(on_selection_change));
+        self
+    }
+
+    /// Set the message to produce when the user moves to another cell with the keyboard, given the
+    /// row and column of the cell.
+    ///
+    /// Once the grid is focused, by pressing it or by focusing it with an operation, the arrow keys
+    /// move from the selected cell by one cell, Home and End move to the ends of its row, and Page
+    /// Up and Page Down move by as many rows as fit in the viewport. Tab and Shift+Tab move as set
+    /// with [`Self::tab_traversal`], and Enter presses the focused cell. Moving to a cell also
+    /// selects it, with the messages set with [`Self::on_select`] and
+    /// [`Self::on_selection_change`], so the grid can be focused as long as any of these messages
+    /// are set, or cells can be pressed. The focused cell is drawn with the ring set with
+    /// [`Self::focus_ring`].
+    #[must_use]
+    pub fn on_focus_cell(mut self, on_focus_cell: impl Fn(usize, usize) -> M + 'a) -> Self {
+        self.on_focus_cell = Some(Rc::new(on_focus_cell));
+        self
+    }
+
+    /// Set how far pressing Tab moves the focused cell, which is one cell by default. Shift+Tab
+    /// moves as far the other way.
+    ///
+    /// Tab moves past the last cell of the grid, and Shift+Tab past its first cell, without being
+    /// captured, so the application can move the focus to the next widget.
+    #[must_use]
+    pub const fn tab_traversal(mut self, tab_traversal: Traversal) -> Self {
+        self.tab_traversal = tab_traversal;
+        self
+    }
+
+    /// Set the border drawn around the focused cell while the grid is focused. By default, a line
+    /// as wide as the drop indicator is drawn in the color of the text.
+    #[must_use]
+    pub fn focus_ring(mut self, focus_ring: impl Into<Border>) -> Self {
+        self.focus_ring = Some(focus_ring.into());
+        self
+    }
+
+    /// Set the message to produce when the user pastes text into the grid with Ctrl+V (or
+    /// Command+V on macOS), given the row and column of the selected cell and the fields of each
+    /// row of the text, which is read as tab-separated values.
+    ///
+    /// Text is only pasted while the grid is focused and has a selected cell. Copying with Ctrl+C
+    /// works the other way around, writing the text of the selected cells set with [`Cell::text`]
+    /// to the clipboard as tab-separated values.
+    #[must_use]
+    pub fn on_paste(mut self, on_paste: impl Fn(usize, usize, Vec<Vec<String>>) -> M + 'a) -> Self {
+        self.on_paste = Some(Rc::new(on_paste));
+        self
+    }
+
+    /// Set the message to produce when the user drags a row to a new position, given the index the
+    /// row was at and the index it should be moved to, as if it was removed from where it was and
+    /// then inserted at the new index.
+    ///
+    /// A row is dragged by pressing any of its cells and moving the cursor up or down, and a line
+    /// is drawn between the rows where the row would be dropped.
+    #[must_use]
+    pub fn on_row_reorder(mut self, on_row_reorder: impl Fn(usize, usize) -> M + 'a) -> Self {
+        self.on_row_reorder = Some(Rc::new(on_row_reorder));
+        self
+    }
+
+    /// Set the message to produce when the user drags a column to a new position, given the index
+    /// the column was at and the index it should be moved to, as if it was removed from where it
+    /// was and then inserted at the new index.
+    ///
+    /// A column is dragged by pressing its cell in the header and moving the cursor left or right,
+    /// so columns can only be reordered in grids with a header. A line is drawn between the columns
+    /// where the column would be dropped.
+    #[must_use]
+    pub fn on_column_reorder(mut self, on_column_reorder: impl Fn(usize, usize) -> M + 'a) -> Self {
+        self.on_column_reorder = Some(Rc::new(on_column_reorder));
+        self
+    }
+
+    /// Set the message to produce when the user resizes a column, given the index of the column and
+    /// its new width, which should be kept and given back to the grid with
+    /// [`Self::column_widths`].
+    ///
+    /// A column is resized by dragging the gutter on its right, and is drawn with its new width
+    /// while it is being dragged.
+    #[must_use]
+    pub fn on_column_resize(mut self, on_column_resize: impl Fn(usize, Pixels) -> M + 'a) -> Self {
+        self.on_column_resize = Some(Rc::new(on_column_resize));
+        self
+    }
+
+    /// Make the grid scroll vertically within a viewport of the given height, only creating the
+    /// cells of the rows that are visible, along with a few rows above and below them.
+    ///
+    /// This keeps grids with many rows fast, since the factories of rows out of view are never
+    /// called. Rows out of view still take up space, so their heights should be fixed. Like with
+    /// [`Self::scrollable`], the header stays in place while the rows scroll.
+    #[must_use]
+    pub fn virtualized(mut self, viewport_height: impl Into<Pixels>) -> Self {
+        self.virtualized = Some(viewport_height.into());
+        self
+    }
+
+    /// Show the given number of skeleton rows after the rows added so far, standing in for rows
+    /// that are not loaded yet, and produce the message given the range of rows they stand for
+    /// once the first of them is less than a viewport away from coming into view, so the rows can
+    /// be loaded and added to the grid in their place.
+    ///
+    /// This lets a [virtualized](Self::virtualized) or [scrollable](Self::scrollable) grid grow
+    /// without end as it is scrolled, by adding the loaded rows and calling this again with the
+    /// next batch. The message is produced once for each range of rows, and as soon as the grid is
+    /// shown if it does not scroll. Skeleton rows come after every other row, whatever order the
+    /// rows are sorted in, and are never filtered out.
+    ///
+    /// The skeleton rows are not rows of the grid: they always follow its last row, so rows added
+    /// later take their place and push them down, and calling this again replaces them.
+    #[must_use]
+    pub fn on_request_rows(
+        mut self,
+        count: usize,
+        on_request_rows: impl Fn(Range<usize>) -> M + 'a,
+    ) -> Self {
+        self.skeleton_rows = count;
+        self.on_request_rows = Some(Rc::new(on_request_rows));
+        self
+    }
+
+    /// Set whether the grid fills the space it is given and scrolls its rows within it, keeping the
+    /// header in place above them. The header scrolls horizontally along with the rows, so its
+    /// cells always line up with the columns below them.
+    #[must_use]
+    pub const fn scrollable(mut self, scrollable: bool) -> Self {
+        self.scrollable = scrollable;
+        self
+    }
+
+    /// Make the grid choose its number of columns from the width it is given when it is laid out,
+    /// fitting as many columns of at least the given width as it can, like
+    /// `repeat(auto-fill, minmax(...))` in CSS. The cells of every row are laid out one after
+    /// another in rows of that many cells, so the grid reflows as its width changes.
+    ///
+    /// The cells keep the rows and columns they were added at in the messages they produce, but
+    /// the grid cannot be moved through with the keyboard and its rows cannot be reordered.
+    #[must_use]
+    pub fn responsive(mut self, min_cell_width: impl Into<Pixels>) -> Self {
+        self.responsive = Some(min_cell_width.into());
+        self
+    }
+
+    /// Keep the given number of leading columns in place while the rest of the columns scroll
+    /// horizontally underneath them, like the frozen columns of a spreadsheet.
+    ///
+    /// This only has an effect on grids that scroll, which are made with [`Self::scrollable`] or
+    /// [`Self::virtualized`].
+    #[must_use]
+    pub const fn frozen_columns(mut self, frozen_columns: usize) -> Self {
+        self.frozen_columns = frozen_columns;
+        self
+    }
+
+    /// Only show the rows on the given page, given as its index and the number of rows on each
+    /// page. The cells of the rows on the page are still created and styled with their indices
+    /// among all of the rows of the grid.
+    ///
+    /// A row of controls for changing pages can be shown below the rows with
+    /// [`Self::on_page_change`].
+    #[must_use]
+    pub fn page(mut self, page: usize, rows_per_page: usize) -> Self {
+        self.pager = Some(Pager::new(page, rows_per_page));
+        self
+    }
+
+    /// Get the number of pages the rows of the grid are split into, which is one if the grid is not
+    /// paginated.
+    #[must_use]
+    pub fn page_count(&self) -> usize {
+        self.pager
+            .map_or(1, |pager| pager.page_count(self.shown_row_count()))
+    }
+
+    /// Find the range of positions of the rows on the page being shown, which is every row if the
+    /// grid is not paginated.
+    fn page_rows(&self) -> Range<usize> {
+        let row_count = self.shown_row_count();
+        self.pager
+            .map_or(0..row_count, |pager| pager.rows(row_count))
+    }
+
+    /// Check whether the row at the given index is shown, passing the filter set with
+    /// [`Self::filter_rows`] if there is one. Rows that are not loaded yet are always shown.
+    fn is_shown(&self, row: usize) -> bool {
+        row >= self.rows.len() || self.row_filter.as_ref().is_none_or(|filter| filter(row))
+    }
+
+    /// Find the rows that are shown, passing the filter set with [`Self::filter_rows`] if there is
+    /// one and not nested under a collapsed row if the rows form a tree, in the order they were
+    /// added.
+    fn shown_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        let mut collapsed: Option<usize> = None;
+        (0..self.rows.len() + self.skeleton_rows).filter(move |&row| {
+            if let Some(row_depth) = &self.row_depth
+                && row < self.rows.len()
+            {
+                let depth = row_depth(row);
+                if collapsed.is_some_and(|collapsed| depth > collapsed) {
+                    return false;
+                }
+                collapsed = (self.has_children(row) && !self.is_expanded(row)).then_some(depth);
+            }
+            self.is_shown(row)
+        })
+    }
+
+    /// Count the rows that are shown. See [`Self::shown_rows`].
+    fn shown_row_count(&self) -> usize {
+        self.shown_rows().count()
+    }
+
+    /// Find the order the rows of the grid are shown in, as the index of the row shown at each
+    /// position. Rows that are hidden by the filter or nested under a collapsed row are left out,
+    /// and rows that are not loaded yet come last, without being sorted.
+    fn row_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = self
+            .shown_rows()
+            .filter(|&row| row < self.rows.len())
+            .collect();
+        if let Some((column, sort_order)) = self.sort
+            && let Some(Some(compare)) = self.sort_keys.get(column)
+        {
+            order.sort_by(|&a, &b| match sort_order {
+                SortOrder::Ascending => compare(a, b),
+                SortOrder::Descending => compare(b, a),
+            });
+        }
+        order.extend(self.loading_rows());
+        order
+    }
+
+    /// Set the definitions of the columns of the grid, which set the header to the titles of the
+    /// columns and sort the rows by the first column with a sort order set.
+    ///
+    /// Pressing the header of a column with a sort key produces the message set with
+    /// [`Self::on_sort`], and the header of the column the rows are sorted by shows an indicator of
+    /// the order they are sorted in. Messages produced by the cells of the grid still give the
+    /// indices the rows were added with.
+    #[must_use]
+    pub fn column_defs(
+        mut self,
+        column_defs: impl IntoIterator<Item = ColumnDef<'a, M, T, R>>,
+    ) -> Self {
+        let mut header = Vec::new();
+        self.sort_keys.clear();
+        self.sort = None;
+        for (column, column_def) in column_defs.into_iter().enumerate() {
+            header.push(Some(column_def.title));
+            if self.sort.is_none()
+                && column_def.sort_key.is_some()
+                && let Some(sort_order) = column_def.sort_order
+            {
+                self.sort = Some((column, sort_order));
+            }
+            self.sort_keys.push(column_def.sort_key);
+        }
+        self.header = Some(header);
+        self
+    }
+
+    /// Set the message to produce when the user presses the header of a column with a sort key,
+    /// given the index of the column and the order the rows should be sorted in, which should be
+    /// given back to the grid with [`ColumnDef::sort_order`].
+    ///
+    /// Pressing the header of the column the rows are already sorted by reverses the order, and
+    /// pressing any other column sorts the rows in ascending order.
+    #[must_use]
+    pub fn on_sort(mut self, on_sort: impl Fn(usize, SortOrder) -> M + 'a) -> Self {
+        self.on_sort = Some(Rc::new(on_sort));
+        self
+    }
+
+    /// Only show the rows that the given function returns true for, given the index of each row.
+    ///
+    /// Hidden rows are kept in the grid, and the cells of the rows that are shown still give the
+    /// indices the rows were added with in the messages they produce.
+    #[must_use]
+    pub fn filter_rows(mut self, filter: impl Fn(usize) -> bool + 'a) -> Self {
+        self.row_filter = Some(Rc::new(filter));
+        self
+    }
+
+    /// Only show the rows that the given function returns true for, given the model the grid was
+    /// created from with [`Self::from_model`] and the index of each row, like with
+    /// [`Self::filter_rows`].
+    #[must_use]
+    pub fn filter_model_rows<G: GridModel<'a, M, T, R>>(
+        self,
+        model: &'a G,
+        filter: impl Fn(&G, usize) -> bool + 'a,
+    ) -> Self {
+        self.filter_rows(move |row| filter(model, row))
+    }
+
+    /// Get the text content of the grid as CSV, with a record for the header, if there is one,
+    /// and for each row in the order the rows are shown in. Rows that are still loading, added
+    /// with [`Self::on_request_rows`], are left out.
+    ///
+    /// The text of each cell is the text set with [`Cell::text`], and cells without text, along
+    /// with empty slots, are left as empty fields.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        self.to_delimited(',')
+    }
+
+    /// Get the text content of the grid as TSV, like [`Self::to_csv`] but with fields separated
+    /// by tabs.
+    #[must_use]
+    pub fn to_tsv(&self) -> String {
+        self.to_delimited('\t')
+    }
+
+    /// Get the text content of the grid with the fields of each record separated by the given
+    /// delimiter, quoting fields that contain the delimiter, quotes or line breaks.
+    fn to_delimited(&self, delimiter: char) -> String {
+        let mut output = String::new();
+        let mut write_record = |row: usize, factories: &[Option<Factory<'a, M, T, R>>]| {
+            let fields = factories.iter().enumerate().map(|(column, factory)| {
+                factory
+                    .as_ref()
+                    .and_then(|factory| factory.create(row, column).text)
+                    .unwrap_or_default()
+            });
+            push_record(&mut output, fields, delimiter);
+        };
+        if let Some(header) = &self.header {
+            write_record(0, header);
+        }
+        for row in self.row_order() {
+            if row < self.rows.len() {
+                write_record(row, &self.factories(row));
+            }
+        }
+        output
+    }
+
+    /// Get the text of the selected cells as tab-separated values, which is copied to the clipboard
+    /// with Ctrl+C. There is a record for each row with selected cells, in the order rows are
+    /// shown across every page, and a field for each column between the first and last columns
+    /// with selected cells. Fields for cells that are not selected are left empty.
+    pub(crate) fn selection_text(&self) -> Option<String> {
+        let mut selection = self.selection.clone();
+        selection.extend(self.selected);
+        let first_column = selection.iter().map(|&(_, column)| column).min()?;
+        let last_column = selection.iter().map(|&(_, column)| column).max()?;
+        let rows: HashSet<usize> = selection.iter().map(|&(row, _)| row).collect();
+        let mut text = String::new();
+        for row in self
+            .row_order()
+            .into_iter()
+            .filter(|row| rows.contains(row))
+        {
+            let factories = self.factories(row);
+            let fields = (first_column..=last_column).map(|column| {
+                factories
+                    .get(column)
+                    .and_then(Option::as_ref)
+                    .filter(|_| selection.contains(&(row, column)))
+                    .and_then(|factory| factory.create(row, column).text)
+                    .unwrap_or_default()
+            });
+            push_record(&mut text, fields, '\t');
+        }
+        Some(text)
+    }
+
+    /// Set the padding around the grid.
+    #[must_use]
+    pub fn padding(mut self, padding: impl Into<Pixels>) -> Self {
+        self.padding = padding.into();
+        self
+    }
+}
+
+impl<
+    'a,
+    M: Clone + 'a,
+    T: text_input::Catalog + container::Catalog + text::Catalog + 'a,
+    R: advanced::text::Renderer + 'a,
+> Grid<'a, M, T, R>
+{
+    /// Set the cell being edited, if any, given as its row and column. The cell being edited is
+    /// swapped for a [`TextInput`] showing the value set with [`Self::edit_value`], so the value
+    /// can be changed with the messages set with [`Self::on_edit`] and [`Self::on_commit`].
+    #[must_use]
+    pub fn editing(mut self, editing: Option<(usize, usize)>) -> Self {
+        self.editing = editing;
+        self.editor = Some(Self::editor);
+        self
+    }
+
+    /// Set the value shown in the cell being edited.
+    #[must_use]
+    pub fn edit_value(mut self, edit_value: impl Into<String>) -> Self {
+        self.edit_value = edit_value.into();
+        self
+    }
+
+    /// Set the message to produce when the value of the cell being edited is changed, given the
+    /// row and column of the cell and the new value. Without this message, the cell being edited
+    /// cannot be changed.
+    #[must_use]
+    pub fn on_edit(mut self, on_edit: impl Fn(usize, usize, String) -> M + 'a) -> Self {
+        self.on_edit = Some(Rc::new(on_edit));
+        self
+    }
+
+    /// Set the message to produce when the value of the cell being edited is submitted, by
+    /// pressing Enter, given the row and column of the cell. The message is not produced while
+    /// the value is rejected by the validator of the cell, set with [`Cell::validator`].
+    #[must_use]
+    pub fn on_commit(mut self, on_commit: impl Fn(usize, usize) -> M + 'a) -> Self {
+        self.on_commit = Some(Rc::new(on_commit));
+        self
+    }
+
+    /// Create the [`TextInput`] the cell at the given row and column is swapped for while it is
+    /// being edited, showing the given error of the value being edited in a tooltip instead of
+    /// committing the value, if any.
+    fn editor(&self, row: usize, column: usize, error: Option<String>) -> Element<'a, M, T, R> {
+        let mut input = TextInput::new("", &self.edit_value);
+        if let Some(on_edit) = self.on_edit.clone() {
+            input = input.on_input(move |value| on_edit(row, column, value));
+        }
+        match (error, &self.on_commit) {
+            (Some(error), _) => Tooltip::new(input, text(error), tooltip::Position::Bottom).into(),
+            (None, Some(on_commit)) => input.on_submit(on_commit(row, column)).into(),
+            (None, None) => input.into(),
+        }
+    }
+}
+
+impl<'a, M: Clone + 'a, T: button::Catalog + text::Catalog + 'a, R: advanced::text::Renderer + 'a>
+    Grid<'a, M, T, R>
+{
+    /// Set the message to produce when the user changes the page of a paginated grid, given the
+    /// index of the new page, which should be given back to the grid with [`Self::page`].
+    ///
+    /// Setting this shows a row of controls below the rows of the grid for moving to the previous
+    /// and next pages.
+    #[must_use]
+    pub fn on_page_change(mut self, on_page_change: impl Fn(usize) -> M + 'a) -> Self {
+        self.on_page_change = Some(Rc::new(on_page_change));
+        self.pager_row = Some(Self::pager_row);
+        self
+    }
+
+    /// Make the rows of the grid form a tree, like an outline or the files of a file browser, given
+    /// how deeply each row is nested and whether each row is expanded, and set the message to
+    /// produce when the user expands or collapses a row, given the index of the row and whether
+    /// it should be expanded.
+    ///
+    /// A row is the parent of the rows right after it that are nested more deeply than it, so
+    /// parents come before their children in the order rows are added, and rows should not be
+    /// sorted. The first cell of each row is indented by how deeply the row is nested, after a
+    /// toggle for expanding and collapsing the row if it has children. The children of a row
+    /// that is collapsed are hidden.
+    #[must_use]
+    pub fn tree_rows(
+        mut self,
+        depth: impl Fn(usize) -> usize + 'a,
+        expanded: impl Fn(usize) -> bool + 'a,
+        on_toggle: impl Fn(usize, bool) -> M + 'a,
+    ) -> Self {
+        self.row_depth = Some(Rc::new(depth));
+        self.row_expanded = Some(Rc::new(expanded));
+        self.on_toggle_row = Some(Rc::new(on_toggle));
+        self.expander = Some(Self::expander);
+        self
+    }
+
+    /// Create the toggle for expanding and collapsing the given row of a tree of rows.
+    fn expander(&self, row: usize) -> Element<'a, M, T, R> {
+        let expanded = self.is_expanded(row);
+        button(
+            text(if expanded { "▾" } else { "▸" })
+                .width(Length::Fill)
+                .align_x(Horizontal::Center),
+        )
+        .on_press_maybe(
+            self.on_toggle_row
+                .as_ref()
+                .map(|on_toggle_row| on_toggle_row(row, !expanded)),
+        )
+        .width(EXPANDER_WIDTH)
+        .padding(0)
+        .into()
+    }
+
+    /// Create the row of controls for changing pages shown below the rows of the grid.
+    fn pager_row(&self) -> Element<'a, M, T, R> {
+        let page = self.pager.map_or(0, |pager| pager.page);
+        let page_count = self.page_count();
+        let change = |page: usize| {
+            self.on_page_change
+                .as_ref()
+                .map(|on_page_change| on_page_change(page))
+        };
+        Row::new()
+            .push(button(text("Previous")).on_press_maybe(page.checked_sub(1).and_then(change)))
+            .push(text(format!("Page {} of {page_count}", page + 1)))
+            .push(
+                button(text("Next")).on_press_maybe(
+                    Some(page + 1)
+                        .filter(|&next| next < page_count)
+                        .and_then(change),
+                ),
+            )
+            .spacing(10)
+            .align_y(Alignment::Center)
+            .into()
+    }
+}
+
+impl<'a, M: 'a, T: text::Catalog + 'a, R: advanced::text::Renderer + 'a> Grid<'a, M, T, R> {
+    /// Set whether the rows of the grid are numbered like the rows of a spreadsheet, in a column
+    /// of their own before the first column. Rows are numbered from one, and keep their numbers
+    /// when they are sorted or filtered. The numbers are styled like the header.
+    ///
+    /// The column of row numbers is not counted as a column of the grid, so the indices of the
+    /// other columns stay the same, and it stays in place if the grid scrolls horizontally.
+    #[must_use]
+    pub fn row_numbers(mut self, row_numbers: bool) -> Self {
+        self.row_numbers = row_numbers.then_some(Self::label);
+        self
+    }
+
+    /// Set whether the columns of the grid are named with letters like the columns of a
+    /// spreadsheet, in a row of their own above the header, if the grid has one. The letters are
+    /// styled like the header.
+    ///
+    /// The row of letters is not counted as a row of the grid, so the indices of the other rows
+    /// stay the same.
+    #[must_use]
+    pub fn column_letters(mut self, column_letters: bool) -> Self {
+        self.column_letters = column_letters.then_some(Self::label);
+        self
+    }
+
+    /// Show the given aggregate of the values of the rows in the given column in the footer of the
+    /// grid, in place of the cell of the footer in that column, given the value of each row in
+    /// the column, if it has one. The grid is given a footer if it has none.
+    ///
+    /// The values of every row that passes the filter set with [`Self::filter_rows`] are
+    /// combined, on every page of a paginated grid, leaving out rows without a value. The
+    /// aggregate is shown in its default format, given by [`Aggregate::format`].
+    #[must_use]
+    pub fn aggregate(
+        self,
+        column: usize,
+        aggregate: Aggregate,
+        value: impl Fn(usize) -> Option<f64> + 'a,
+    ) -> Self {
+        self.aggregate_formatted(column, aggregate, value, aggregate.format())
+    }
+
+    /// Show the given aggregate of the values of the rows in the given column in the footer of the
+    /// grid in the given format, like [`Self::aggregate`].
+    #[must_use]
+    pub fn aggregate_formatted(
+        mut self,
+        column: usize,
+        aggregate: Aggregate,
+        value: impl Fn(usize) -> Option<f64> + 'a,
+        format: CellFormat,
+    ) -> Self {
+        self.aggregates
+            .push((column, aggregate, Rc::new(value), format));
+        self.aggregate_label = Some(Self::label);
+        self
+    }
+
+    /// Create the element showing a label of a row or column, or an aggregate of a column.
+    fn label(label: String) -> Element<'a, M, T, R> {
+        text(label).into()
+    }
+}
+
+impl<'a, M: 'a, T: Catalog + 'a, R: advanced::Renderer + 'a> Grid<'a, M, T, R> {
+    /// Set the style of the frame drawn behind the grid, with a background, a border, and a
+    /// shadow, so the grid can be drawn as a card without wrapping it in a container. The frame
+    /// covers the whole area of the grid, padding included, beneath all of its cells.
+    #[must_use]
+    pub fn style(self, style: impl Fn(&T) -> style::Style + 'a) -> Self
+    where
+        T::Class<'a>: From<style::StyleFn<'a, T>>,
+    {
+        self.class(T::Class::from(Box::new(style)))
+    }
+
+    /// Set the class of the style of the frame drawn behind the grid from the catalog of the
+    /// theme. See [`Self::style`].
+    #[must_use]
+    pub fn class(mut self, class: impl Into<T::Class<'a>>) -> Self
+    where
+        T::Class<'a>: 'a,
+    {
+        let class = class.into();
+        self.frame_style = Some(Rc::new(move |theme: &T| theme.style(&class)));
+        self
+    }
+}
+
+#[cfg(feature = "csv")]
+impl<'a, M: 'a, T: text::Catalog + 'a, R: advanced::text::Renderer + 'a> Grid<'a, M, T, R> {
+    /// Create a new grid from CSV read from the given reader, turning each record into a row of
+    /// text cells. If `has_header` is true, the first record becomes the header of the grid.
+    ///
+    /// Records may have different numbers of fields, leaving the rows of shorter records short.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the CSV cannot be read or parsed.
+    pub fn from_csv_reader(
+        reader: impl std::io::Read,
+        has_header: bool,
+    ) -> Result<Self, csv::Error> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(has_header)
+            .flexible(true)
+            .from_reader(reader);
+        let text_cell = |field: &str| {
+            let field = field.to_owned();
+            Factory::from_factory(move || Cell::from(text(field.clone())).text(field.clone()))
+        };
+        let mut grid = Self::new();
+        if has_header {
+            grid = grid.header(reader.headers()?.iter().map(text_cell));
+        }
+        for record in reader.records() {
+            grid.push_line(record?.iter().map(text_cell));
+        }
+        Ok(grid)
+    }
+
+    /// Create a new grid from the given CSV, like [`Self::from_csv_reader`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the CSV cannot be parsed.
+    pub fn from_csv_str(csv: &str, has_header: bool) -> Result<Self, csv::Error> {
+        Self::from_csv_reader(csv.as_bytes(), has_header)
+    }
+}
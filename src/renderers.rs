@@ -0,0 +1,84 @@
+//! Renderers turning values of any type into the cells of a [`Grid`](crate::Grid), so a grid of
+//! values of mixed types can be created without matching on the type of each column.
+use crate::Cell;
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt,
+    rc::Rc,
+};
+
+/// A function creating a cell from a value of the type it is registered for, which creates no cell
+/// for values of other types.
+type Renderer<'a, M, T, R> = Rc<dyn Fn(&dyn Any) -> Option<Cell<'a, M, T, R>> + 'a>;
+
+/// A registry of the renderers of values of each type, given to a grid with
+/// [`Grid::from_values`](crate::Grid::from_values).
+///
+/// ```ignore
+/// let registry = Registry::new()
+///     .register::<f64>(|value| Cell::from(text(format!("{value:.2}"))))
+///     .register::<String>(|value| Cell::from(text(value.clone())));
+/// ```
+pub struct Registry<'a, M, T, R> {
+    renderers: HashMap<TypeId, Renderer<'a, M, T, R>>,
+}
+
+impl<M, T, R> Clone for Registry<'_, M, T, R> {
+    fn clone(&self) -> Self {
+        Self {
+            renderers: self.renderers.clone(),
+        }
+    }
+}
+
+impl<M, T, R> Default for Registry<'_, M, T, R> {
+    fn default() -> Self {
+        Self {
+            renderers: HashMap::new(),
+        }
+    }
+}
+
+impl<M, T, R> fmt::Debug for Registry<'_, M, T, R> {
+    /// Format the registry, leaving out its renderers.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Registry")
+            .field("renderers", &self.renderers.len())
+            .finish()
+    }
+}
+
+impl<'a, M, T, R> Registry<'a, M, T, R> {
+    /// Create a new registry without any renderers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the function creating the cells of values of the type `V`, replacing the renderer
+    /// registered for the type before, if any.
+    #[must_use]
+    pub fn register<V: Any>(mut self, renderer: impl Fn(&V) -> Cell<'a, M, T, R> + 'a) -> Self {
+        self.renderers.insert(
+            TypeId::of::<V>(),
+            Rc::new(move |value| value.downcast_ref().map(&renderer)),
+        );
+        self
+    }
+
+    /// Check whether a renderer is registered for values of the type `V`.
+    #[must_use]
+    pub fn contains<V: Any>(&self) -> bool {
+        self.renderers.contains_key(&TypeId::of::<V>())
+    }
+
+    /// Create the cell of the given value with the renderer registered for its type, if any. A
+    /// boxed value should be given as `&*value`, since the type of `&value` is the box itself.
+    #[must_use]
+    pub fn render(&self, value: &dyn Any) -> Option<Cell<'a, M, T, R>> {
+        self.renderers
+            .get(&value.type_id())
+            .and_then(|renderer| renderer(value))
+    }
+}
@@ -0,0 +1,88 @@
+//! The style of the frame drawn behind a [`Grid`](crate::Grid), beneath all of its cells.
+use iced::{Background, Border, Color, Shadow, Theme, border};
+
+/// The style of the frame of a grid, drawn over the whole area of the grid, padding included.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Style {
+    /// The background of the grid.
+    pub background: Option<Background>,
+    /// The border around the grid.
+    pub border: Border,
+    /// The shadow cast by the grid.
+    pub shadow: Shadow,
+}
+
+impl Style {
+    /// Set the background of the grid.
+    #[must_use]
+    pub fn background(self, background: impl Into<Background>) -> Self {
+        Self {
+            background: Some(background.into()),
+            ..self
+        }
+    }
+
+    /// Set the border around the grid.
+    #[must_use]
+    pub fn border(self, border: impl Into<Border>) -> Self {
+        Self {
+            border: border.into(),
+            ..self
+        }
+    }
+
+    /// Set the shadow cast by the grid.
+    #[must_use]
+    pub fn shadow(self, shadow: impl Into<Shadow>) -> Self {
+        Self {
+            shadow: shadow.into(),
+            ..self
+        }
+    }
+}
+
+impl From<Color> for Style {
+    /// Create a style with the given color as its background.
+    fn from(color: Color) -> Self {
+        Self::default().background(color)
+    }
+}
+
+/// The theme catalog of the frame of a grid, like the catalogs of the widgets of `iced`.
+pub trait Catalog {
+    /// The item class of the catalog.
+    type Class<'a>;
+
+    /// The style of a class.
+    fn style(&self, class: &Self::Class<'_>) -> Style;
+}
+
+/// A function giving the style of the frame of a grid for a theme.
+pub type StyleFn<'a, Theme> = Box<dyn Fn(&Theme) -> Style + 'a>;
+
+impl Catalog for Theme {
+    type Class<'a> = StyleFn<'a, Self>;
+
+    fn style(&self, class: &Self::Class<'_>) -> Style {
+        class(self)
+    }
+}
+
+/// A frame with no background, border, or shadow, which is how a grid is drawn without a style.
+#[must_use]
+pub fn transparent<Theme>(_theme: &Theme) -> Style {
+    Style::default()
+}
+
+/// A card with the weak background color of the palette of the theme and rounded corners.
+#[must_use]
+pub fn card(theme: &Theme) -> Style {
+    let palette = theme.extended_palette();
+    Style::default()
+        .background(palette.background.weak.color)
+        .border(
+            border::rounded(8)
+                .color(palette.background.strong.color)
+                .width(1),
+        )
+}